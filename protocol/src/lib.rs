@@ -180,6 +180,14 @@ pub struct QuestCompleteEvent {
     pub elapsed_time_in_secs: u32,
 }
 
+/// Sent when a quest is loaded into.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QuestStartEvent {
+    pub quest_id: u32,
+    /// Difficulty of the quest, `None` if the hook could not read it.
+    pub difficulty: Option<u32>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct OnUpdateSBAEvent {
     pub actor_index: u32,
@@ -209,6 +217,113 @@ pub struct OnDeathEvent {
     pub death_counter: u32,
 }
 
+/// Sent when an actor starts casting a skill.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SkillCastEvent {
+    pub source: Actor,
+    pub skill_id: u32,
+    /// Cooldown of the skill in seconds, if it could be read.
+    pub cooldown: Option<f32>,
+}
+
+/// Sent when a status effect (buff or debuff) is applied to or removed from an actor.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StatusChangeEvent {
+    /// Actor that applied the status, if known.
+    pub source: Option<Actor>,
+    pub target: Actor,
+    /// Hash ID of the status.
+    pub status_id: u32,
+    /// Skill that applied the status, if known.
+    pub skill_id: Option<u32>,
+    /// True when the status is applied, false when it is removed.
+    pub active: bool,
+}
+
+/// Sent when an enemy dies.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OnEnemyDeathEvent {
+    pub actor_index: u32,
+    /// Hash ID of the enemy.
+    pub actor_type: u32,
+}
+
+/// A state an enemy can be put in by the party.
+// @TODO: Break and overdrive, once it's known where the enemy instance keeps them. Only stuns can be detected
+// from the damage event so far.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EnemyCondition {
+    Stun,
+}
+
+/// Sent when an enemy enters or leaves a condition.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EnemyConditionEvent {
+    pub target: Actor,
+    pub condition: EnemyCondition,
+    /// True when the condition starts, false when it ends.
+    pub active: bool,
+    /// Actor whose hit started the condition, if known.
+    pub trigger: Option<Actor>,
+}
+
+/// Install state of a single hook.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookState {
+    Installed,
+    /// The hook's setup failed, e.g. its signature didn't match this version of the game.
+    Failed,
+    /// The function for the hook hasn't been located yet, so it was never attempted.
+    Unavailable,
+}
+
+/// Whether a single hook was installed, and why not if it failed.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HookInstallStatus {
+    /// Name of the hook, e.g. `damage` or `sba_update`.
+    pub name: String,
+    pub state: HookState,
+    /// Reason the hook could not be installed.
+    pub error: Option<String>,
+}
+
+impl HookInstallStatus {
+    pub fn is_installed(&self) -> bool {
+        self.state == HookState::Installed
+    }
+}
+
+/// Sent once per client after the hook has finished installing, describing which hooks are active.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HookStatusEvent {
+    pub hooks: Vec<HookInstallStatus>,
+}
+
+impl HookStatusEvent {
+    /// Hooks that failed to install. Hooks that are unavailable are not included.
+    pub fn failed(&self) -> impl Iterator<Item = &HookInstallStatus> {
+        self.hooks
+            .iter()
+            .filter(|hook| hook.state == HookState::Failed)
+    }
+
+    /// Whether the named hook is installed.
+    pub fn is_installed(&self, name: &str) -> bool {
+        self.hooks
+            .iter()
+            .any(|hook| hook.name == name && hook.is_installed())
+    }
+}
+
+/// Sent when the hook had to drop messages because the client could not keep up.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MessagesDroppedEvent {
+    /// Number of messages dropped since the last report.
+    pub dropped: u64,
+    /// Number of messages dropped since the hook was loaded.
+    pub total_dropped: u64,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum Message {
     OnAreaEnter(AreaEnterEvent),
@@ -220,4 +335,65 @@ pub enum Message {
     OnContinueSBAChain(OnContinueSBAChainEvent),
     PlayerLoadEvent(PlayerLoadEvent),
     OnDeathEvent(OnDeathEvent),
+    HookStatus(HookStatusEvent),
+    MessagesDropped(MessagesDroppedEvent),
+    OnQuestStart(QuestStartEvent),
+    OnEnemyCondition(EnemyConditionEvent),
+    OnEnemyDeath(OnEnemyDeathEvent),
+    OnStatusChange(StatusChangeEvent),
+    OnSkillCast(SkillCastEvent),
+    /// A message this version doesn't know about, read from a log saved by a newer version.
+    /// `raw` is the encoded payload, kept as is so that saving the log again doesn't lose it. It's empty for unit
+    /// variants, which have no payload.
+    /// This is never sent by the hook, so it has to stay the last variant.
+    Unknown {
+        tag: String,
+        raw: Vec<u8>,
+    },
+}
+
+/// A message as sent over the pipe.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Frame {
+    /// Incremented by one for every event sent by the hook. Dropped events still use up a number, so that the
+    /// parser can detect gaps.
+    /// Messages sent directly to a single client (e.g. `HookStatus`) don't have a sequence number.
+    pub sequence: Option<u64>,
+    pub message: Message,
+}
+
+impl Frame {
+    /// Builds a serialized frame around a message that was already serialized with bincode.
+    /// The result is the same as serializing the whole `Frame`, since bincode writes struct fields back to back.
+    pub fn encode(sequence: Option<u64>, message: &[u8]) -> bincode::Result<Vec<u8>> {
+        let mut bytes = bincode::serialize(&sequence)?;
+        bytes.extend_from_slice(message);
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encoded_frame_matches_serialized_frame() {
+        let message = Message::OnDeathEvent(OnDeathEvent {
+            actor_index: 1,
+            death_counter: 2,
+        });
+        let message_bytes = bincode::serialize(&message).unwrap();
+
+        for sequence in [None, Some(0), Some(u64::MAX)] {
+            let frame = Frame {
+                sequence,
+                message: message.clone(),
+            };
+
+            assert_eq!(
+                Frame::encode(sequence, &message_bytes).unwrap(),
+                bincode::serialize(&frame).unwrap()
+            );
+        }
+    }
 }
@@ -0,0 +1,135 @@
+//! Checks the hook's signatures against a copy of the game executable, without running the game.
+//!
+//! Usage: `sigcheck <path to granblue_fantasy_relink.exe> [path to signatures.json]`
+
+use std::{path::PathBuf, process::ExitCode};
+
+use anyhow::{anyhow, Context, Result};
+use pelite::{
+    pe64::{Pe, PeFile},
+    FileMap,
+};
+use signatures::{find_matches, SignatureDatabase, SignatureKind, SignatureMatch};
+
+fn describe_match(file: PeFile, kind: SignatureKind, found: &SignatureMatch) -> String {
+    match kind {
+        SignatureKind::Function => {
+            format!(
+                "match at {:#010x} -> function {:#010x}",
+                found.rva, found.target
+            )
+        }
+        SignatureKind::U32 => match file.derva_copy::<u32>(found.target) {
+            Ok(value) => format!("match at {:#010x} -> value {:#x}", found.rva, value),
+            Err(e) => format!("match at {:#010x} -> unreadable value ({})", found.rva, e),
+        },
+        SignatureKind::U8 => match file.derva_copy::<u8>(found.target) {
+            Ok(value) => format!("match at {:#010x} -> value {:#x}", found.rva, value),
+            Err(e) => format!("match at {:#010x} -> unreadable value ({})", found.rva, e),
+        },
+    }
+}
+
+fn run() -> Result<bool> {
+    let mut args = std::env::args().skip(1);
+
+    let exe_path: PathBuf = args
+        .next()
+        .ok_or(anyhow!(
+            "Usage: sigcheck <granblue_fantasy_relink.exe> [signatures.json]"
+        ))?
+        .into();
+
+    let database = match args.next() {
+        Some(path) => {
+            let json = std::fs::read_to_string(&path)
+                .with_context(|| format!("Could not read signatures file {}", path))?;
+            SignatureDatabase::from_json(&json)?
+        }
+        None => SignatureDatabase::embedded(),
+    };
+
+    let map = FileMap::open(&exe_path)
+        .with_context(|| format!("Could not open {}", exe_path.display()))?;
+    let file = PeFile::from_bytes(&map).context("Not a 64-bit PE file")?;
+
+    println!(
+        "Checking {} signatures (version {}) against {}",
+        database.iter().count(),
+        database.version,
+        exe_path.display()
+    );
+    println!();
+
+    let mut matched = 0;
+    let mut total = 0;
+
+    for (name, signature) in database.iter() {
+        total += 1;
+
+        let mut lines = Vec::new();
+        let mut found_any = false;
+
+        for (i, signature_pattern) in signature.patterns.iter().enumerate() {
+            let matches = find_matches(file, signature_pattern)?;
+
+            match matches.len() {
+                0 => lines.push(format!("  pattern {}: no matches", i + 1)),
+                1 => lines.push(format!("  pattern {}: 1 match", i + 1)),
+                n => lines.push(format!(
+                    "  pattern {}: {} matches (ambiguous, the hook may pick the wrong one)",
+                    i + 1,
+                    n
+                )),
+            }
+
+            for found in matches.iter() {
+                lines.push(format!(
+                    "    {}",
+                    describe_match(file, signature.kind, found)
+                ));
+            }
+
+            found_any |= !matches.is_empty();
+        }
+
+        if found_any {
+            matched += 1;
+        }
+
+        println!(
+            "[{}] {} ({:?})",
+            if found_any { " OK " } else { "FAIL" },
+            name,
+            signature.kind
+        );
+
+        if let Some((i, found)) = signature.find(file)? {
+            lines.push(format!(
+                "  the hook uses pattern {}: {}",
+                i + 1,
+                describe_match(file, signature.kind, &found)
+            ));
+        }
+
+        for line in lines {
+            println!("{}", line);
+        }
+    }
+
+    println!();
+    println!("{}/{} signatures matched.", matched, total);
+
+    Ok(matched == total)
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
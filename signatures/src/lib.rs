@@ -0,0 +1,291 @@
+/*!
+Byte signatures used by the hook to find functions and offsets in the game executable.
+
+Signatures live in a versioned JSON file (`signatures.json`) so that a game update can be fixed by
+editing data. Each signature has one or more patterns, which are tried in order until one matches. If the
+matching pattern matches more than once, its first match is used.
+
+Patterns use the `pelite` pattern syntax, and must save the address of interest with `'`:
+- For `function` signatures, the saved address is the function to hook (usually through `e8 $ { ' }`).
+- For `u32` / `u8` signatures, the saved address is where the value (an offset) is read from.
+*/
+
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use pelite::{
+    pattern,
+    pe64::{Pe, Rva},
+};
+use serde::Deserialize;
+
+/// Signatures that ship with the hook.
+pub const DEFAULT_SIGNATURES: &str = include_str!("../signatures.json");
+
+/// Version of the signatures file format that this crate understands.
+pub const SIGNATURES_VERSION: u32 = 1;
+
+/// What a signature's saved address points to.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureKind {
+    /// A function that gets hooked.
+    Function,
+    /// A 32-bit value, usually a struct offset.
+    U32,
+    /// An 8-bit value, usually a struct offset.
+    U8,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Signature {
+    pub kind: SignatureKind,
+    /// Patterns to try in order, the first one that matches wins.
+    pub patterns: Vec<String>,
+}
+
+impl Signature {
+    /// Finds the signature in the PE image, trying each pattern in order.
+    /// Returns the index of the pattern that matched, along with its first match.
+    pub fn find<'a, P: Pe<'a>>(&self, pe: P) -> Result<Option<(usize, SignatureMatch)>> {
+        for (i, signature_pattern) in self.patterns.iter().enumerate() {
+            // If a pattern matches more than once, the first match is used.
+            if let Some(found) = find_matches(pe, signature_pattern)?.first() {
+                return Ok(Some((i, *found)));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// A single match of a pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignatureMatch {
+    /// RVA of where the pattern matched.
+    pub rva: Rva,
+    /// RVA of the saved address (`'`) in the pattern.
+    pub target: Rva,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SignatureDatabase {
+    pub version: u32,
+    signatures: BTreeMap<String, Signature>,
+}
+
+impl SignatureDatabase {
+    /// Parses and validates a signatures file.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let database: Self = serde_json::from_str(json)?;
+        database.validate()?;
+        Ok(database)
+    }
+
+    /// Signatures that are compiled in.
+    pub fn embedded() -> Self {
+        Self::from_json(DEFAULT_SIGNATURES).expect("Embedded signatures should be valid")
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.version != SIGNATURES_VERSION {
+            return Err(anyhow!(
+                "Unsupported signatures version: {} (expected {})",
+                self.version,
+                SIGNATURES_VERSION
+            ));
+        }
+
+        for (name, signature) in self.signatures.iter() {
+            if signature.patterns.is_empty() {
+                return Err(anyhow!("Signature {} has no patterns", name));
+            }
+
+            for signature_pattern in signature.patterns.iter() {
+                let pattern = pattern::parse(signature_pattern)
+                    .map_err(|e| anyhow!("Invalid pattern for {}: {}", name, e))?;
+
+                // Slot 0 is the start of the match, slot 1 is the first saved address.
+                if pattern::save_len(&pattern) < 2 {
+                    return Err(anyhow!(
+                        "Pattern for {} does not save an address: {}",
+                        name,
+                        signature_pattern
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Gets a signature by name.
+    pub fn get(&self, name: &str) -> Result<&Signature> {
+        self.signatures
+            .get(name)
+            .ok_or(anyhow!("Unknown signature: {}", name))
+    }
+
+    /// Iterates over all signatures, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Signature)> {
+        self.signatures.iter()
+    }
+}
+
+/// Finds every match of the given pattern in the code sections of the PE image.
+pub fn find_matches<'a, P: Pe<'a>>(pe: P, signature_pattern: &str) -> Result<Vec<SignatureMatch>> {
+    let pattern = pattern::parse(signature_pattern)?;
+    let mut save = vec![0; pattern::save_len(&pattern).max(2)];
+    let mut matches = pe.scanner().matches_code(&pattern);
+    let mut found = Vec::new();
+
+    while matches.next(&mut save) {
+        found.push(SignatureMatch {
+            rva: save[0],
+            target: save[1],
+        });
+    }
+
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pelite::pe64::PeView;
+
+    const HEADERS_SIZE: usize = 0x200;
+    const IMAGE_SIZE: usize = 0x400;
+
+    #[repr(C, align(8))]
+    struct Image([u8; IMAGE_SIZE]);
+
+    /// Builds a minimal mapped PE64 image with no sections, whose code range holds `code`.
+    fn image_with_code(code: &[u8]) -> Image {
+        fn write(image: &mut Image, offset: usize, bytes: &[u8]) {
+            image.0[offset..offset + bytes.len()].copy_from_slice(bytes);
+        }
+
+        let mut image = Image([0; IMAGE_SIZE]);
+
+        // DOS header, pointing to the NT headers right after it.
+        write(&mut image, 0x00, b"MZ");
+        write(&mut image, 0x3c, &0x40u32.to_le_bytes());
+
+        // NT headers: AMD64 file header with a 240 byte optional header.
+        write(&mut image, 0x40, b"PE\0\0");
+        write(&mut image, 0x44, &0x8664u16.to_le_bytes());
+        write(&mut image, 0x54, &240u16.to_le_bytes());
+
+        // Optional header: magic, size of code, base of code, size of image, size of headers.
+        write(&mut image, 0x58, &0x20bu16.to_le_bytes());
+        write(
+            &mut image,
+            0x5c,
+            &((IMAGE_SIZE - HEADERS_SIZE) as u32).to_le_bytes(),
+        );
+        write(&mut image, 0x6c, &(HEADERS_SIZE as u32).to_le_bytes());
+        write(&mut image, 0x90, &(IMAGE_SIZE as u32).to_le_bytes());
+        write(&mut image, 0x94, &(HEADERS_SIZE as u32).to_le_bytes());
+
+        write(&mut image, HEADERS_SIZE, code);
+        image
+    }
+
+    #[test]
+    fn falls_back_to_the_next_pattern() {
+        let database = SignatureDatabase::embedded();
+        let signature = database.get("on_check_sba_collision").unwrap();
+        assert!(signature.patterns.len() > 1);
+
+        // A call to the function at +0x100, followed by a jump that is further away than the primary pattern expects.
+        #[rustfmt::skip]
+        let code = [
+            0xe8, 0xfb, 0x00, 0x00, 0x00,
+            0x84, 0xc0,
+            0x0f, 0x85, 0x10, 0x01, 0x00, 0x00,
+            0x8b, 0x8e, 0x20, 0x01, 0xff, 0xff,
+        ];
+        let image = image_with_code(&code);
+        let view = PeView::from_bytes(&image.0).unwrap();
+
+        let (pattern_index, found) = signature.find(view).unwrap().unwrap();
+        assert_eq!(pattern_index, 1);
+        assert_eq!(found.rva, HEADERS_SIZE as Rva);
+        assert_eq!(found.target, HEADERS_SIZE as Rva + 0x100);
+
+        // With the jump the primary pattern expects, the primary pattern wins.
+        let mut code = code;
+        code[9] = 0xf0;
+        code[10] = 0x00;
+        let image = image_with_code(&code);
+        let view = PeView::from_bytes(&image.0).unwrap();
+
+        let (pattern_index, _) = signature.find(view).unwrap().unwrap();
+        assert_eq!(pattern_index, 0);
+    }
+
+    #[test]
+    fn picks_the_first_of_multiple_matches() {
+        let signature = Signature {
+            kind: SignatureKind::Function,
+            patterns: vec!["e8 $ { ' } 84 c0".to_string()],
+        };
+
+        #[rustfmt::skip]
+        let code = [
+            0xe8, 0x10, 0x00, 0x00, 0x00, 0x84, 0xc0,
+            0xe8, 0x20, 0x00, 0x00, 0x00, 0x84, 0xc0,
+        ];
+        let image = image_with_code(&code);
+        let view = PeView::from_bytes(&image.0).unwrap();
+
+        assert_eq!(find_matches(view, &signature.patterns[0]).unwrap().len(), 2);
+
+        let (_, found) = signature.find(view).unwrap().unwrap();
+        assert_eq!(found.target, HEADERS_SIZE as Rva + 0x15);
+
+        let empty = image_with_code(&[]);
+        let view = PeView::from_bytes(&empty.0).unwrap();
+        assert_eq!(signature.find(view).unwrap(), None);
+    }
+
+    #[test]
+    fn embedded_signatures_are_valid() {
+        let database = SignatureDatabase::embedded();
+
+        assert_eq!(
+            database.get("process_damage_event").unwrap().kind,
+            SignatureKind::Function
+        );
+        assert_eq!(
+            database.get("weapon_offset").unwrap().kind,
+            SignatureKind::U8
+        );
+        assert!(database.get("does_not_exist").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_signatures() {
+        // Unknown version
+        assert!(SignatureDatabase::from_json(r#"{ "version": 0, "signatures": {} }"#).is_err());
+
+        // No patterns
+        assert!(SignatureDatabase::from_json(
+            r#"{ "version": 1, "signatures": { "a": { "kind": "function", "patterns": [] } } }"#
+        )
+        .is_err());
+
+        // Pattern doesn't save an address
+        assert!(SignatureDatabase::from_json(
+            r#"{ "version": 1, "signatures": { "a": { "kind": "u32", "patterns": ["48 8b ?"] } } }"#
+        )
+        .is_err());
+
+        // Pattern doesn't parse
+        assert!(SignatureDatabase::from_json(
+            r#"{ "version": 1, "signatures": { "a": { "kind": "u32", "patterns": ["e8 $ { '"] } } }"#
+        )
+        .is_err());
+    }
+}
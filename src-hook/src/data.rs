@@ -0,0 +1,63 @@
+use std::path::{Path, PathBuf};
+
+use log::{info, warn};
+
+/// Path to a user-editable data file override: `%APPDATA%\gbfr-logs\<file_name>`
+fn override_path(file_name: &str) -> Option<PathBuf> {
+    let mut path = dirs::data_dir()?;
+    path.push("gbfr-logs");
+    path.push(file_name);
+    Some(path)
+}
+
+/// Loads a data file override if it exists, returning `None` if it's missing or invalid.
+pub fn load_override<T>(file_name: &str, parse: impl Fn(&str) -> anyhow::Result<T>) -> Option<T> {
+    load_from(&override_path(file_name)?, parse)
+}
+
+/// Loads a data file, returning `None` if it's missing or invalid.
+fn load_from<T>(path: &Path, parse: impl Fn(&str) -> anyhow::Result<T>) -> Option<T> {
+    if !path.exists() {
+        return None;
+    }
+
+    match std::fs::read_to_string(path)
+        .map_err(anyhow::Error::from)
+        .and_then(|contents| parse(&contents))
+    {
+        Ok(data) => {
+            info!("Loaded {:?}", path);
+            Some(data)
+        }
+        Err(e) => {
+            warn!("Ignoring invalid {:?}: {:?}", path, e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(contents: &str) -> anyhow::Result<u32> {
+        Ok(contents.trim().parse()?)
+    }
+
+    #[test]
+    fn ignores_missing_and_invalid_files() {
+        let dir = std::env::temp_dir().join(format!("gbfr-logs-data-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let valid = dir.join("valid.json");
+        let invalid = dir.join("invalid.json");
+        std::fs::write(&valid, "42").unwrap();
+        std::fs::write(&invalid, "not a number").unwrap();
+
+        assert_eq!(load_from(&valid, parse), Some(42));
+        assert_eq!(load_from(&invalid, parse), None);
+        assert_eq!(load_from(&dir.join("missing.json"), parse), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,6 +1,213 @@
-use tokio::sync::broadcast;
+/*!
+Handoff of messages from the detours, which run on the game's threads, to the pipe writer thread.
 
-use protocol::Message;
+Detours serialize their message and push it into a bounded lock-free queue, so the game never waits on a lock
+or on the pipe. If the writer falls behind and the queue is full, the message is dropped and counted instead.
+The writer then numbers each frame in the order it pops them, skipping over dropped messages so that the parser
+can see the gap. The writer thread sleeps while the queue is empty, and is woken up by the detours.
+*/
 
-pub type Tx = broadcast::Sender<Message>;
-pub type Rx = broadcast::Receiver<Message>;
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard, OnceLock,
+    },
+    thread::Thread,
+    time::{Duration, Instant},
+};
+
+use crossbeam_queue::ArrayQueue;
+use protocol::{Message, PlayerLoadEvent, QuestStartEvent};
+
+use crate::metrics::Metrics;
+
+/// Number of messages that can be waiting for the writer, about a second of a heavy multi-hit fight.
+const QUEUE_CAPACITY: usize = 4096;
+
+/// Latest state that was sent, so that clients connecting late can catch up.
+#[derive(Debug, Default)]
+struct Snapshot {
+    /// Latest player load event, keyed by actor index.
+    players: BTreeMap<u32, PlayerLoadEvent>,
+    /// Quest that was loaded last, until its result screen is shown.
+    quest_start: Option<QuestStartEvent>,
+}
+
+impl Snapshot {
+    fn update(&mut self, message: &Message) {
+        match message {
+            // Players are loaded again after entering an area.
+            Message::OnAreaEnter(_) => {
+                self.players.clear();
+            }
+            Message::PlayerLoadEvent(event) => {
+                self.players.insert(event.actor_index, event.clone());
+            }
+            Message::OnQuestStart(event) => {
+                self.quest_start = Some(event.clone());
+            }
+            Message::OnQuestComplete(_) => {
+                self.quest_start = None;
+            }
+            _ => {}
+        }
+    }
+}
+
+struct Shared {
+    /// Messages serialized with bincode, waiting to be sent.
+    queue: ArrayQueue<Vec<u8>>,
+    /// Messages dropped since the writer last checked.
+    dropped: AtomicU64,
+    /// Only locked for the rare messages that change the snapshot, and when a client connects. Detours for
+    /// frequent messages (e.g. damage) never lock it.
+    snapshot: Mutex<Snapshot>,
+    metrics: Metrics,
+    /// Thread that sends the queued messages, woken up whenever a message is queued.
+    writer: OnceLock<Thread>,
+}
+
+impl Shared {
+    fn snapshot(&self) -> MutexGuard<'_, Snapshot> {
+        self.snapshot.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Sending half, cloned into every hook.
+#[derive(Clone)]
+pub struct Tx {
+    shared: Arc<Shared>,
+}
+
+/// Receiving half, owned by the writer thread.
+pub struct Rx {
+    shared: Arc<Shared>,
+}
+
+pub fn channel() -> (Tx, Rx) {
+    let shared = Arc::new(Shared {
+        queue: ArrayQueue::new(QUEUE_CAPACITY),
+        dropped: AtomicU64::new(0),
+        snapshot: Mutex::new(Snapshot::default()),
+        metrics: Metrics::default(),
+        writer: OnceLock::new(),
+    });
+
+    (
+        Tx {
+            shared: shared.clone(),
+        },
+        Rx { shared },
+    )
+}
+
+/// Measures the time a detour spends on its own work, from when it starts until it has queued its message.
+/// The call to the game's original function is left out, as that time isn't added by the hook.
+pub struct DetourTimer<'a> {
+    tx: &'a Tx,
+    start: Instant,
+    excluded: Duration,
+}
+
+impl DetourTimer<'_> {
+    /// Runs the game's original function without counting the time it takes.
+    pub fn exclude<T>(&mut self, original: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let value = original();
+        self.excluded += start.elapsed();
+        value
+    }
+
+    /// Queues a message for the writer, recording the time spent so far under the message's type.
+    /// Returns false if the message had to be dropped.
+    pub fn send(&self, message: Message) -> bool {
+        let queued = self.tx.queue(&message);
+
+        self.tx
+            .shared
+            .metrics
+            .record(&message, self.start.elapsed().saturating_sub(self.excluded));
+
+        queued
+    }
+}
+
+impl Tx {
+    /// Starts timing a detour, call this when the detour starts.
+    pub fn timer(&self) -> DetourTimer<'_> {
+        DetourTimer {
+            tx: self,
+            start: Instant::now(),
+            excluded: Duration::ZERO,
+        }
+    }
+
+    /// Queues a message for the writer, returns false if it had to be dropped.
+    pub fn send(&self, message: Message) -> bool {
+        self.timer().send(message)
+    }
+
+    fn queue(&self, message: &Message) -> bool {
+        if matches!(
+            message,
+            Message::OnAreaEnter(_)
+                | Message::PlayerLoadEvent(_)
+                | Message::OnQuestStart(_)
+                | Message::OnQuestComplete(_)
+        ) {
+            self.shared.snapshot().update(message);
+        }
+
+        let queued = match protocol::bincode::serialize(message) {
+            Ok(bytes) => self.shared.queue.push(bytes).is_ok(),
+            Err(_) => false,
+        };
+
+        if !queued {
+            self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+
+        if let Some(writer) = self.shared.writer.get() {
+            writer.unpark();
+        }
+
+        queued
+    }
+}
+
+impl Rx {
+    /// Makes the current thread the writer, which is woken up whenever a message is queued.
+    pub fn register_writer(&self) {
+        let _ = self.shared.writer.set(std::thread::current());
+    }
+
+    /// Sleeps until a message is queued or the timeout passes. Has to be called from the writer thread.
+    pub fn wait(&self, timeout: Duration) {
+        std::thread::park_timeout(timeout);
+    }
+
+    /// Takes the next serialized message, if any.
+    pub fn pop(&self) -> Option<Vec<u8>> {
+        self.shared.queue.pop()
+    }
+
+    /// Number of messages dropped since the last call.
+    pub fn take_dropped(&self) -> u64 {
+        self.shared.dropped.swap(0, Ordering::Relaxed)
+    }
+
+    /// Players loaded in the current area.
+    pub fn players(&self) -> Vec<PlayerLoadEvent> {
+        self.shared.snapshot().players.values().cloned().collect()
+    }
+
+    /// Quest that is currently loaded, if it was seen being loaded.
+    pub fn quest_start(&self) -> Option<QuestStartEvent> {
+        self.shared.snapshot().quest_start.clone()
+    }
+
+    pub fn metrics(&self) -> &Metrics {
+        &self.shared.metrics
+    }
+}
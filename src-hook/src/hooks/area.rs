@@ -2,7 +2,7 @@ use std::sync::atomic::Ordering;
 
 use crate::{event, hooks::globals::QUEST_STATE_PTR, process::Process};
 use anyhow::{anyhow, Result};
-use protocol::Message;
+use protocol::{AreaEnterEvent, Message};
 use retour::static_detour;
 
 type OnEnterAreaFunc = unsafe extern "system" fn(u32, *const usize, u8, *const usize) -> usize;
@@ -11,7 +11,7 @@ static_detour! {
     static OnEnterArea: unsafe extern "system" fn(u32, *const usize, u8, *const usize) -> usize;
 }
 
-const ON_ENTER_AREA_SIG: &str = "e8 $ { ' } c5 ? ? ? c5 f8 29 45 ? c7 45 ? ? ? ? ?";
+const ON_ENTER_AREA_SIG: &str = "on_enter_area";
 
 /// Handles tracking whenever the player enters a new area.
 #[derive(Clone)]
@@ -50,25 +50,28 @@ impl OnAreaEnterHook {
         #[cfg(feature = "console")]
         println!("on enter area");
 
-        let quest_state_ptr = QUEST_STATE_PTR.load(Ordering::Relaxed);
+        let timer = self.tx.timer();
+        let _ = timer.send(Message::OnAreaEnter(current_area_event()));
 
-        if !quest_state_ptr.is_null() {
-            let quest_state = unsafe { quest_state_ptr.read() };
+        unsafe { OnEnterArea.call(a1, a2, a3, a4) }
+    }
+}
 
-            let quest_id = quest_state.quest_id;
-            let timer = quest_state.elapsed_time;
+/// Builds an area event from the last known quest state, with a quest ID of 0 if no quest was loaded yet.
+pub fn current_area_event() -> AreaEnterEvent {
+    let quest_state_ptr = QUEST_STATE_PTR.load(Ordering::Relaxed);
 
-            let _ = self.tx.send(Message::OnAreaEnter(protocol::AreaEnterEvent {
-                last_known_quest_id: quest_id,
-                last_known_elapsed_time_in_secs: timer,
-            }));
-        } else {
-            let _ = self.tx.send(Message::OnAreaEnter(protocol::AreaEnterEvent {
-                last_known_quest_id: 0,
-                last_known_elapsed_time_in_secs: 0,
-            }));
-        }
+    if !quest_state_ptr.is_null() {
+        let quest_state = unsafe { quest_state_ptr.read() };
 
-        unsafe { OnEnterArea.call(a1, a2, a3, a4) }
+        AreaEnterEvent {
+            last_known_quest_id: quest_state.quest_id,
+            last_known_elapsed_time_in_secs: quest_state.elapsed_time,
+        }
+    } else {
+        AreaEnterEvent {
+            last_known_quest_id: 0,
+            last_known_elapsed_time_in_secs: 0,
+        }
     }
 }
@@ -1,7 +1,7 @@
 use std::ptr::NonNull;
 
 use anyhow::{anyhow, Result};
-use protocol::{ActionType, Actor, DamageEvent, Message};
+use protocol::{ActionType, Actor, DamageEvent, EnemyCondition, EnemyConditionEvent, Message};
 use retour::static_detour;
 
 use crate::{event, hooks::ffi::DamageInstance, process::Process};
@@ -23,7 +23,8 @@ pub struct OnProcessDamageHook {
     tx: event::Tx,
 }
 
-const PROCESS_DAMAGE_EVENT_SIG: &str = "e8 $ { ' } 66 83 bc 24 ? ? ? ? ?";
+const PROCESS_DAMAGE_EVENT_SIG: &str = "process_damage_event";
+const PROCESS_DOT_EVENT_SIG: &str = "process_dot_event";
 
 impl OnProcessDamageHook {
     pub fn new(tx: event::Tx) -> Self {
@@ -53,6 +54,8 @@ impl OnProcessDamageHook {
     }
 
     fn run(&self, a1: *const usize, a2: *const usize, a3: *const usize, a4: u8) -> usize {
+        let mut timer = self.tx.timer();
+
         // Target is the instance of the actor being damaged.
         // For example: Instance of the Em2700 class.
         let target_specified_instance_ptr: usize = unsafe { *(*a1.byte_add(0x08) as *const usize) };
@@ -63,7 +66,7 @@ impl OnProcessDamageHook {
                 .read()
         };
 
-        let original_value = unsafe { ProcessDamageEvent.call(a1, a2, a3, a4) };
+        let original_value = timer.exclude(|| unsafe { ProcessDamageEvent.call(a1, a2, a3, a4) });
 
         let current_stun_value = unsafe {
             (target_specified_instance_ptr as *const f32)
@@ -126,19 +129,23 @@ impl OnProcessDamageHook {
             Some(added_stun_value)
         };
 
+        let source = Actor {
+            index: source_idx,
+            actor_type: source_type_id,
+            parent_index: source_parent_idx,
+            parent_actor_type: source_parent_type_id,
+        };
+
+        let target = Actor {
+            index: target_idx,
+            actor_type: target_type_id,
+            parent_index: target_idx,
+            parent_actor_type: target_type_id,
+        };
+
         let event = Message::DamageEvent(DamageEvent {
-            source: Actor {
-                index: source_idx,
-                actor_type: source_type_id,
-                parent_index: source_parent_idx,
-                parent_actor_type: source_parent_type_id,
-            },
-            target: Actor {
-                index: target_idx,
-                actor_type: target_type_id,
-                parent_index: target_idx,
-                parent_actor_type: target_type_id,
-            },
+            source: source.clone(),
+            target: target.clone(),
             damage,
             flags,
             action_id: action_type,
@@ -147,7 +154,29 @@ impl OnProcessDamageHook {
             stun_value,
         });
 
-        let _ = self.tx.send(event);
+        let _ = timer.send(event);
+
+        // The stun gauge empties when it fills up and the enemy is stunned, and it doesn't build up again until
+        // the stun is over. So a hit that lowers the gauge started a stun, and the first hit that raises the
+        // emptied gauge again means the stun has ended. That's the earliest the end can be seen, so stuns are
+        // counted until the next hit after them. Fresh enemies also start with an empty gauge, and players have
+        // no stun gauge, the parser drops those changes as it knows the party and which enemies are stunned.
+        let stun_change = if current_stun_value < previous_stun_value {
+            Some((true, Some(source)))
+        } else if previous_stun_value <= 0.0 && current_stun_value > 0.0 {
+            Some((false, None))
+        } else {
+            None
+        };
+
+        if let Some((active, trigger)) = stun_change {
+            let _ = timer.send(Message::OnEnemyCondition(EnemyConditionEvent {
+                target,
+                condition: EnemyCondition::Stun,
+                active,
+                trigger,
+            }));
+        }
 
         original_value
     }
@@ -166,9 +195,7 @@ impl OnProcessDotHook {
     pub fn setup(&self, process: &Process) -> Result<()> {
         let cloned_self = self.clone();
 
-        if let Ok(process_dot_evt) =
-            process.search_address("44 89 74 24 ? 48 ? ? ? ? 48 ? ? e8 $ { ' } 4c")
-        {
+        if let Ok(process_dot_evt) = process.search_address(PROCESS_DOT_EVENT_SIG) {
             #[cfg(feature = "console")]
             println!("Found process dot event");
 
@@ -190,7 +217,8 @@ impl OnProcessDotHook {
     // A1+0x30->sourceEntityInfo : CEntityInfo (Source entity of the DoT, who applied it)
     // A1+0x50->duration : float (How much time is left for the DoT)
     fn run(&self, dot_instance: *const usize, a2: *const usize) -> usize {
-        let original_value = unsafe { ProcessDotEvent.call(dot_instance, a2) };
+        let mut timer = self.tx.timer();
+        let original_value = timer.exclude(|| unsafe { ProcessDotEvent.call(dot_instance, a2) });
 
         // @TODO(false): There's a better way to check null pointers with Option type, but I'm too dumb to figure it out right now.
         let target_info = unsafe { dot_instance.byte_add(0x18).read() } as *const usize;
@@ -239,7 +267,7 @@ impl OnProcessDotHook {
             damage_cap: None,
         });
 
-        let _ = self.tx.send(event);
+        let _ = timer.send(event);
 
         original_value
     }
@@ -9,7 +9,7 @@ static_detour! {
     static OnDeathEvent: unsafe extern "system" fn(*const usize) -> usize;
 }
 
-const ON_DEATH_EVENT_SIG: &str = "e8 $ { ' } 49 ? ? 48 ? ? ? ? ? ? 83 78 ? ?";
+const ON_DEATH_EVENT_SIG: &str = "on_death_event";
 
 #[derive(Clone)]
 pub struct OnDeathHook {
@@ -44,7 +44,8 @@ impl OnDeathHook {
         #[cfg(feature = "console")]
         println!("on death");
 
-        let ret = unsafe { OnDeathEvent.call(a1) };
+        let mut timer = self.tx.timer();
+        let ret = timer.exclude(|| unsafe { OnDeathEvent.call(a1) });
 
         let entity_ptr = unsafe { a1.byte_add(0x10).read() as *const usize };
         let actor_index = unsafe { entity_ptr.byte_add(0x170).read() } as u32;
@@ -55,7 +56,7 @@ impl OnDeathHook {
             death_counter,
         });
 
-        let _ = self.tx.send(event);
+        let _ = timer.send(event);
 
         ret
     }
@@ -15,7 +15,7 @@ pub static SBA_OFFSET: AtomicU32 = AtomicU32::new(0);
 
 pub fn setup_globals(process: &Process) -> Result<()> {
     let player_data_offset = process
-        .search_slice::<u32>("3d b0 e0 7a 88 0f ? ? ? ? ? b8 b0 e0 7a 88 48 8d 8e '")
+        .search_slice::<u32>("player_data_offset")
         .context("Could not find player_data_offset")?;
 
     #[cfg(feature = "console")]
@@ -24,7 +24,7 @@ pub fn setup_globals(process: &Process) -> Result<()> {
     PLAYER_DATA_OFFSET.store(player_data_offset, std::sync::atomic::Ordering::Relaxed);
 
     let sigil_offset = process
-        .search_slice::<u32>("8b 01 eb 02 31 c0 49 8b 8c 24 ' ? ? ? ? 89 81 ? ? ? ?")
+        .search_slice::<u32>("sigil_offset")
         .context("Could not find sigil offset")?;
 
     #[cfg(feature = "console")]
@@ -36,7 +36,7 @@ pub fn setup_globals(process: &Process) -> Result<()> {
     );
 
     let weapon_offset = process
-        .search_slice::<u8>("48 ? ? ' ? 48 ? ? ? 48 ? ? e8 ? ? ? ? 31 ?")
+        .search_slice::<u8>("weapon_offset")
         .context("Could not find weapon offset")?;
 
     #[cfg(feature = "console")]
@@ -48,7 +48,7 @@ pub fn setup_globals(process: &Process) -> Result<()> {
     );
 
     let overmastery_offset = process
-        .search_slice::<u32>("49 8D 8C 24 ' ? ? ? ? 48 8D 93 ? ? ? ? E8 ? ? ? ?")
+        .search_slice::<u32>("overmastery_offset")
         .context("Could not find overmastery offset")?;
 
     #[cfg(feature = "console")]
@@ -60,7 +60,7 @@ pub fn setup_globals(process: &Process) -> Result<()> {
     );
 
     let sba_offset = process
-        .search_slice::<u32>("7E ? C5 FA 59 81 ? ? ? ? 48 81 C1 ' ? ? ? ? C5 F8 54 0D ? ? ? ?")
+        .search_slice::<u32>("sba_offset")
         .context("Could not find sba offset")?;
 
     #[cfg(feature = "console")]
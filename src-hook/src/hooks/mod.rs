@@ -1,7 +1,12 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use death::OnDeathHook;
+use log::{info, warn};
+use protocol::{HookInstallStatus, HookState, HookStatusEvent};
 
-use crate::{event, process::Process};
+use crate::{
+    event,
+    process::{self, Process},
+};
 
 use self::{
     area::OnAreaEnterHook,
@@ -19,38 +24,141 @@ mod damage;
 mod death;
 mod ffi;
 mod globals;
+mod parents;
 mod player;
 mod quest;
 mod sba;
 
+pub use area::current_area_event;
+
 type GetEntityHashID0x58 = unsafe extern "system" fn(*const usize, *const u32) -> *const usize;
 
-pub fn setup_hooks(tx: event::Tx) -> Result<()> {
+/// Installs every hook independently, so that a missing signature only disables the hooks that depend on it.
+/// Returns a report of which hooks were installed, to be sent to the parser.
+pub fn setup_hooks(tx: event::Tx) -> Result<HookStatusEvent> {
     let process = Process::with_name("granblue_fantasy_relink.exe")?;
 
-    globals::setup_globals(&process)?;
+    process::setup_signatures();
+    parents::setup_actor_parents();
+
+    let mut report = HookReport::default();
+
+    let has_globals = report.install("globals", || globals::setup_globals(&process));
 
     /* Damage Events */
-    OnProcessDamageHook::new(tx.clone()).setup(&process)?;
-    OnProcessDotHook::new(tx.clone()).setup(&process)?;
-    OnDeathHook::new(tx.clone()).setup(&process)?;
+    report.install("damage", || {
+        OnProcessDamageHook::new(tx.clone()).setup(&process)
+    });
+    report.install("damage_over_time", || {
+        OnProcessDotHook::new(tx.clone()).setup(&process)
+    });
+    report.install("death", || OnDeathHook::new(tx.clone()).setup(&process));
+    // @TODO: Locate the function for this, the parser already handles its messages.
+    report.unavailable("enemy_death");
 
     /* Player Data */
-    OnLoadPlayerHook::new(tx.clone()).setup(&process)?;
+    report.install_with_globals("player_load", has_globals, || {
+        OnLoadPlayerHook::new(tx.clone()).setup(&process)
+    });
 
     /* Quest + Area Tracking */
-    OnAreaEnterHook::new(tx.clone()).setup(&process)?;
-    OnLoadQuestHook::new().setup(&process)?;
-    OnQuestCompleteHook::new(tx.clone()).setup(&process)?;
+    report.install("area_enter", || {
+        OnAreaEnterHook::new(tx.clone()).setup(&process)
+    });
+    report.install("quest_load", || {
+        OnLoadQuestHook::new(tx.clone()).setup(&process)
+    });
+    report.install("quest_complete", || {
+        OnQuestCompleteHook::new(tx.clone()).setup(&process)
+    });
+
+    /* Skills */
+    // @TODO: Locate the function for this, and read the skill's cooldown.
+    report.unavailable("skill_cast");
+
+    /* Buffs + Debuffs */
+    // @TODO: Locate the functions for these, and read the status's hash ID from its instance.
+    report.unavailable("status_apply");
+    report.unavailable("status_remove");
 
     /* SBA */
-    OnHandleSBAUpdateHook::new(tx.clone()).setup(&process)?;
-    OnRemoteSBAUpdateHook::new(tx.clone()).setup(&process)?;
-    OnAttemptSBAHook::new(tx.clone()).setup(&process)?;
-    OnCheckSBACollisionHook::new(tx.clone()).setup(&process)?;
-    OnContinueSBAChainHook::new(tx.clone()).setup(&process)?;
+    report.install_with_globals("sba_update", has_globals, || {
+        OnHandleSBAUpdateHook::new(tx.clone()).setup(&process)
+    });
+    report.install_with_globals("sba_remote_update", has_globals, || {
+        OnRemoteSBAUpdateHook::new(tx.clone()).setup(&process)
+    });
+    report.install("sba_attempt", || {
+        OnAttemptSBAHook::new(tx.clone()).setup(&process)
+    });
+    report.install("sba_collision", || {
+        OnCheckSBACollisionHook::new(tx.clone()).setup(&process)
+    });
+    report.install("sba_chain", || {
+        OnContinueSBAChainHook::new(tx.clone()).setup(&process)
+    });
+
+    Ok(HookStatusEvent {
+        hooks: report.hooks,
+    })
+}
 
-    Ok(())
+/// Collects the install result of each hook.
+#[derive(Default)]
+struct HookReport {
+    hooks: Vec<HookInstallStatus>,
+}
+
+impl HookReport {
+    /// Runs the setup for a hook and records whether it succeeded.
+    fn install(&mut self, name: &str, setup: impl FnOnce() -> Result<()>) -> bool {
+        let (state, error) = match setup() {
+            Ok(_) => {
+                info!("Installed {} hook", name);
+                (HookState::Installed, None)
+            }
+            Err(e) => {
+                warn!("Could not install {} hook: {:?}", name, e);
+                (HookState::Failed, Some(format!("{:#}", e)))
+            }
+        };
+
+        self.hooks.push(HookInstallStatus {
+            name: name.to_string(),
+            state,
+            error,
+        });
+
+        state == HookState::Installed
+    }
+
+    /// Same as `install`, but skips hooks that read offsets from `globals` when those couldn't be found.
+    fn install_with_globals(
+        &mut self,
+        name: &str,
+        has_globals: bool,
+        setup: impl FnOnce() -> Result<()>,
+    ) -> bool {
+        if has_globals {
+            self.install(name, setup)
+        } else {
+            self.install(name, || Err(anyhow!("Skipped, global offsets are missing")))
+        }
+    }
+
+    /// Records a hook whose function hasn't been located yet, so that it shows up as not installed.
+    fn unavailable(&mut self, name: &str) {
+        info!(
+            "Skipped {} hook, its function hasn't been located yet",
+            name
+        );
+
+        self.hooks.push(HookInstallStatus {
+            name: name.to_string(),
+            state: HookState::Unavailable,
+            error: None,
+        });
+    }
 }
 
 #[inline(always)]
@@ -75,43 +183,13 @@ pub fn actor_idx(actor_ptr: *const usize) -> u32 {
 }
 
 // Returns the parent entity of the source entity if necessary.
+// The parent pointer offsets for each child actor type are loaded from `data/actor-parents.json`.
 #[inline(always)]
 pub fn get_source_parent(source_type_id: u32, source: *const usize) -> Option<(u32, u32)> {
-    match source_type_id {
-        // Pl0700Ghost -> Pl0700
-        0x2AF678E8 => {
-            let parent_instance = parent_specified_instance_at(source, 0xE48)?;
+    let offset = parents::actor_parents().parent_offset(source_type_id)?;
+    let parent_instance = parent_specified_instance_at(source, offset)?;
 
-            Some((actor_type_id(parent_instance), actor_idx(parent_instance)))
-        }
-        // Pl0700GhostSatellite -> Pl0700
-        0x8364C8BC => {
-            let parent_instance = parent_specified_instance_at(source, 0x508)?;
-
-            Some((actor_type_id(parent_instance), actor_idx(parent_instance)))
-        }
-        // Wp1890: Cagliostro's Ouroboros Dragon Sled -> Pl1800
-        0xC9F45042 => {
-            let parent_instance = parent_specified_instance_at(source, 0x578)?;
-            Some((actor_type_id(parent_instance), actor_idx(parent_instance)))
-        }
-        // Pl2000: Id's Dragon Form -> Pl1900
-        0xF5755C0E => {
-            let parent_instance = parent_specified_instance_at(source, 0xD488)?;
-            Some((actor_type_id(parent_instance), actor_idx(parent_instance)))
-        }
-        // Wp2290: Seofon's Avatar
-        0x5B1AB457 => {
-            let parent_instance = parent_specified_instance_at(source, 0x500)?;
-            Some((actor_type_id(parent_instance), actor_idx(parent_instance)))
-        }
-        // Pl0600PlantRose
-        0x69C0CA71 => {
-            let parent_instance = parent_specified_instance_at(source, 0x7E0)?;
-            Some((actor_type_id(parent_instance), actor_idx(parent_instance)))
-        }
-        _ => None,
-    }
+    Some((actor_type_id(parent_instance), actor_idx(parent_instance)))
 }
 
 // Returns the specified instance of the parent entity.
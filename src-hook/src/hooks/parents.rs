@@ -0,0 +1,168 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+
+use crate::data;
+
+/// Parent pointer offsets that ship with the hook.
+const DEFAULT_ACTOR_PARENTS: &str = include_str!("../../data/actor-parents.json");
+
+/// Version of the actor parents file format that this hook understands.
+const ACTOR_PARENTS_VERSION: u32 = 1;
+
+/// Largest offset we accept, anything bigger is most likely a typo.
+const MAX_PARENT_OFFSET: usize = 0x10000;
+
+static ACTOR_PARENTS: OnceLock<ActorParents> = OnceLock::new();
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ActorParentEntry {
+    /// Human-readable name of the child actor, e.g. `Pl0700Ghost`.
+    name: String,
+    /// Hash ID of the child actor type, as a hex string.
+    actor_type: String,
+    /// Offset from the child's instance to the parent's entity pointer, as a hex string.
+    parent_offset: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ActorParentsFile {
+    version: u32,
+    parents: Vec<ActorParentEntry>,
+}
+
+/// Maps child actor type hashes to the offset of their parent entity pointer.
+#[derive(Debug, Default)]
+pub struct ActorParents {
+    offsets: HashMap<u32, usize>,
+}
+
+impl ActorParents {
+    /// Parses and validates an actor parents file.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let file: ActorParentsFile = serde_json::from_str(json)?;
+
+        if file.version != ACTOR_PARENTS_VERSION {
+            return Err(anyhow!(
+                "Unsupported actor parents version: {} (expected {})",
+                file.version,
+                ACTOR_PARENTS_VERSION
+            ));
+        }
+
+        let mut offsets = HashMap::new();
+
+        for entry in file.parents {
+            let actor_type = parse_hex(&entry.actor_type)
+                .and_then(|value| u32::try_from(value).ok())
+                .ok_or(anyhow!("Invalid actor type for {}", entry.name))?;
+
+            let parent_offset = parse_hex(&entry.parent_offset)
+                .map(|value| value as usize)
+                .ok_or(anyhow!("Invalid parent offset for {}", entry.name))?;
+
+            if parent_offset == 0
+                || parent_offset > MAX_PARENT_OFFSET
+                || parent_offset % std::mem::size_of::<usize>() != 0
+            {
+                return Err(anyhow!(
+                    "Parent offset {:#x} for {} is out of range or unaligned",
+                    parent_offset,
+                    entry.name
+                ));
+            }
+
+            if offsets.insert(actor_type, parent_offset).is_some() {
+                return Err(anyhow!(
+                    "Duplicate actor type {:#x} ({})",
+                    actor_type,
+                    entry.name
+                ));
+            }
+        }
+
+        Ok(ActorParents { offsets })
+    }
+
+    /// Returns the parent pointer offset for the given actor type, if it has one.
+    #[inline(always)]
+    pub fn parent_offset(&self, actor_type: u32) -> Option<usize> {
+        self.offsets.get(&actor_type).copied()
+    }
+}
+
+fn parse_hex(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let digits = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .unwrap_or(value);
+
+    u64::from_str_radix(digits, 16).ok()
+}
+
+/// Loads the actor parents table, preferring `%APPDATA%\gbfr-logs\actor-parents.json` if it is valid.
+pub fn setup_actor_parents() {
+    let actor_parents = data::load_override("actor-parents.json", ActorParents::from_json)
+        .unwrap_or_else(|| {
+            ActorParents::from_json(DEFAULT_ACTOR_PARENTS)
+                .expect("Embedded actor parents should be valid")
+        });
+
+    let _ = ACTOR_PARENTS.set(actor_parents);
+}
+
+/// Returns the loaded actor parents table, or an empty one if it hasn't been set up yet.
+#[inline(always)]
+pub fn actor_parents() -> &'static ActorParents {
+    ACTOR_PARENTS.get_or_init(ActorParents::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parents(actor_type: &str, parent_offset: &str) -> Result<ActorParents> {
+        ActorParents::from_json(&format!(
+            r#"{{ "version": 1, "parents": [{{ "name": "Test", "actorType": "{actor_type}", "parentOffset": "{parent_offset}" }}] }}"#
+        ))
+    }
+
+    #[test]
+    fn parses_hex_with_or_without_prefix() {
+        assert_eq!(parse_hex("0xE48"), Some(0xE48));
+        assert_eq!(parse_hex("0XE48"), Some(0xE48));
+        assert_eq!(parse_hex(" e48 "), Some(0xE48));
+        assert_eq!(parse_hex("0x"), None);
+        assert_eq!(parse_hex("E48h"), None);
+    }
+
+    #[test]
+    fn embedded_actor_parents_are_valid() {
+        let parents = ActorParents::from_json(DEFAULT_ACTOR_PARENTS).unwrap();
+
+        assert_eq!(parents.parent_offset(0x2AF678E8), Some(0xE48));
+        assert_eq!(parents.parent_offset(0x26A4848A), None);
+    }
+
+    #[test]
+    fn rejects_invalid_entries() {
+        assert!(parents("0x2AF678E8", "0xE48").is_ok());
+        assert!(parents("0x100000000", "0xE48").is_err());
+        assert!(parents("Ghost", "0xE48").is_err());
+        assert!(parents("0x2AF678E8", "0").is_err());
+        assert!(parents("0x2AF678E8", "0xE4C").is_err());
+        assert!(parents("0x2AF678E8", "0x20000").is_err());
+
+        assert!(ActorParents::from_json(r#"{ "version": 2, "parents": [] }"#).is_err());
+        assert!(ActorParents::from_json(
+            r#"{ "version": 1, "parents": [
+                { "name": "A", "actorType": "0x1", "parentOffset": "0x8" },
+                { "name": "B", "actorType": "0x1", "parentOffset": "0x10" }
+            ] }"#
+        )
+        .is_err());
+    }
+}
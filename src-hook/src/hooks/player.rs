@@ -20,6 +20,8 @@ static_detour! {
     static OnLoadPlayer: unsafe extern "system" fn(*const usize) -> usize;
 }
 
+const ON_LOAD_PLAYER_SIG: &str = "on_load_player";
+
 #[derive(Clone)]
 pub struct OnLoadPlayerHook {
     tx: event::Tx,
@@ -33,9 +35,7 @@ impl OnLoadPlayerHook {
     pub fn setup(&self, process: &Process) -> Result<()> {
         let cloned_self = self.clone();
 
-        if let Ok(on_load_player_original) =
-            process.search_address("49 89 ce e8 $ { ' } 31 ff 85 c0 ? ? ? ? ? ? 49 8b 46 28")
-        {
+        if let Ok(on_load_player_original) = process.search_address(ON_LOAD_PLAYER_SIG) {
             #[cfg(feature = "console")]
             println!("Found on load player");
 
@@ -55,7 +55,8 @@ impl OnLoadPlayerHook {
         #[cfg(feature = "console")]
         println!("on load player: {:p}", a1);
 
-        let ret = unsafe { OnLoadPlayer.call(a1) };
+        let mut timer = self.tx.timer();
+        let ret = timer.exclude(|| unsafe { OnLoadPlayer.call(a1) });
 
         let player_idx = unsafe { a1.byte_add(0x170).read() } as u32;
 
@@ -178,7 +179,7 @@ impl OnLoadPlayerHook {
             #[cfg(feature = "console")]
             println!("sending player load event: {:?}", payload);
 
-            let _ = self.tx.send(payload);
+            let _ = timer.send(payload);
         }
 
         ret
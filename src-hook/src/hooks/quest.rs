@@ -18,18 +18,31 @@ static_detour! {
     static OnShowResultScreen: unsafe extern "system" fn(*const usize) -> usize;
 }
 
-const ON_LOAD_QUEST_STATE: &str =
-    "48 8b 0d ? ? ? ? e8 $ { ' } c5 fb 12 ? ? ? ? ? c5 f8 11 ? ? ? ? ? c5 f8 11 ? ? ? ? ? 48 83 c4 48";
-const ON_SHOW_RESULT_SCREEN_SIG: &str =
-    "e8 $ { ' } b8 ? ? ? ? 23 87 ? ? 00 00 3d 00 00 60 00 0f 94 c0";
+const ON_LOAD_QUEST_STATE: &str = "on_load_quest_state";
+const ON_SHOW_RESULT_SCREEN_SIG: &str = "on_show_result_screen";
+
+/// Reads the quest ID and elapsed time from the last loaded quest state.
+fn read_quest_state() -> Option<(u32, u32)> {
+    let quest_state_ptr = QUEST_STATE_PTR.load(Ordering::Relaxed);
+
+    if quest_state_ptr.is_null() {
+        return None;
+    }
+
+    let quest_state = unsafe { quest_state_ptr.read() };
+
+    Some((quest_state.quest_id, quest_state.elapsed_time))
+}
 
 /// Called while loading into a quest.
 #[derive(Clone)]
-pub struct OnLoadQuestHook {}
+pub struct OnLoadQuestHook {
+    tx: event::Tx,
+}
 
 impl OnLoadQuestHook {
-    pub fn new() -> Self {
-        OnLoadQuestHook {}
+    pub fn new(tx: event::Tx) -> Self {
+        OnLoadQuestHook { tx }
     }
 
     pub fn setup(&self, process: &Process) -> Result<()> {
@@ -55,7 +68,8 @@ impl OnLoadQuestHook {
         #[cfg(feature = "console")]
         println!("on load quest state");
 
-        let ret = unsafe { OnLoadQuestState.call(a1) };
+        let mut timer = self.tx.timer();
+        let ret = timer.exclude(|| unsafe { OnLoadQuestState.call(a1) });
         let quest_state_ptr = unsafe { a1.byte_add(0x1D8) } as *mut QuestState;
 
         if quest_state_ptr.is_null() {
@@ -64,6 +78,14 @@ impl OnLoadQuestHook {
 
         QUEST_STATE_PTR.store(quest_state_ptr, std::sync::atomic::Ordering::Relaxed);
 
+        let quest_id = unsafe { quest_state_ptr.read() }.quest_id;
+
+        // @TODO: Find where the quest difficulty is stored.
+        let _ = timer.send(Message::OnQuestStart(protocol::QuestStartEvent {
+            quest_id,
+            difficulty: None,
+        }));
+
         ret
     }
 }
@@ -102,22 +124,13 @@ impl OnQuestCompleteHook {
         #[cfg(feature = "console")]
         println!("on show result screen");
 
-        let quest_state_ptr = QUEST_STATE_PTR.load(Ordering::Relaxed);
+        let timer = self.tx.timer();
 
-        if !quest_state_ptr.is_null() {
-            #[cfg(feature = "console")]
-            println!("quest_state_ptr: {:p}", quest_state_ptr);
-
-            let quest_state = unsafe { quest_state_ptr.read() };
-            let quest_id = quest_state.quest_id;
-            let timer = quest_state.elapsed_time;
-
-            let _ = self
-                .tx
-                .send(Message::OnQuestComplete(protocol::QuestCompleteEvent {
-                    quest_id,
-                    elapsed_time_in_secs: timer,
-                }));
+        if let Some((quest_id, elapsed_time_in_secs)) = read_quest_state() {
+            let _ = timer.send(Message::OnQuestComplete(protocol::QuestCompleteEvent {
+                quest_id,
+                elapsed_time_in_secs,
+            }));
         }
 
         unsafe { OnShowResultScreen.call(a1) }
@@ -23,12 +23,11 @@ static_detour! {
     static OnRemoteSBAUpdate: unsafe extern "system" fn(*const usize, *const usize, f32, f32) -> usize;
 }
 
-const ON_HANDLE_SBA_UPDATE_SIG: &str = "e8 $ { ' } c5 fa 10 46 ? c5 f8 2e 86 80 00 00 00";
-const ON_ATTEMPT_SBA_SIG: &str = "e8 $ { ' } 48 8d 8e ? ? ff ff c7 44 24 38 00 00 80 3f";
-const ON_CHECK_SBA_COLLISION_SIG: &str = "e8 $ { ' } 84 c0 0f 85 f0 00 00 ? 8b 8e ? ? ff ff";
-const ON_CONTINUE_SBA_CHAIN_SIG: &str = "e8 $ { ' } 48 8b 53 ? 48 8d 82 ? ? ? ?";
-const ON_HANDLE_REMOTE_SBA_UPDATE_SIG: &str =
-    "48 8b 8f ? ? ? ? 4c 89 e2 e8 $ { ' } e9 ? ? ? ? 48 81 c7 ? ? ? ? 48 89 f9";
+const ON_HANDLE_SBA_UPDATE_SIG: &str = "on_handle_sba_update";
+const ON_ATTEMPT_SBA_SIG: &str = "on_attempt_sba";
+const ON_CHECK_SBA_COLLISION_SIG: &str = "on_check_sba_collision";
+const ON_CONTINUE_SBA_CHAIN_SIG: &str = "on_continue_sba_chain";
+const ON_HANDLE_REMOTE_SBA_UPDATE_SIG: &str = "on_handle_remote_sba_update";
 
 /// Gets called when your SBA gauge value needs to update with a given value.
 #[derive(Clone)]
@@ -63,6 +62,8 @@ impl OnHandleSBAUpdateHook {
     }
 
     fn run(&self, a1: *const usize, a2: f32, a3: u32, a4: u8, a5: u32, a6: u8) -> usize {
+        let mut timer = self.tx.timer();
+
         let sba_offset = SBA_OFFSET.load(Ordering::Relaxed);
 
         let entity_ptr = unsafe { a1.byte_sub(sba_offset as usize) };
@@ -75,7 +76,7 @@ impl OnHandleSBAUpdateHook {
         let sba_value_ptr = unsafe { a1.byte_add(0x7C) } as *const f32;
         let old_sba_value = unsafe { sba_value_ptr.read() };
 
-        let ret = unsafe { OnSBAUpdate.call(a1, a2, a3, a4, a5, a6) };
+        let ret = timer.exclude(|| unsafe { OnSBAUpdate.call(a1, a2, a3, a4, a5, a6) });
 
         let new_sba_value = unsafe { sba_value_ptr.read() };
         let sba_added = f32::max(new_sba_value - old_sba_value, 0.0);
@@ -88,7 +89,7 @@ impl OnHandleSBAUpdateHook {
                 actor_index: source_parent_idx,
             });
 
-            let _ = self.tx.send(payload);
+            let _ = timer.send(payload);
         } else {
             let payload = Message::OnUpdateSBA(protocol::OnUpdateSBAEvent {
                 actor_index: source_parent_idx,
@@ -96,7 +97,7 @@ impl OnHandleSBAUpdateHook {
                 sba_added,
             });
 
-            let _ = self.tx.send(payload);
+            let _ = timer.send(payload);
         }
 
         ret
@@ -134,7 +135,9 @@ impl OnAttemptSBAHook {
     }
 
     fn run(&self, a1: *const usize, a2: f32) -> usize {
-        let ret = unsafe { OnSBAAttempt.call(a1, a2) };
+        let mut timer = self.tx.timer();
+
+        let ret = timer.exclude(|| unsafe { OnSBAAttempt.call(a1, a2) });
 
         let entity_ptr = unsafe { a1.byte_add(0x10).read() } as *const usize;
 
@@ -150,7 +153,7 @@ impl OnAttemptSBAHook {
             actor_index: source_parent_idx,
         });
 
-        let _ = self.tx.send(payload);
+        let _ = timer.send(payload);
 
         ret
     }
@@ -192,7 +195,9 @@ impl OnCheckSBACollisionHook {
     }
 
     fn run(&self, a1: *const usize, a2: f32) -> usize {
-        let ret = unsafe { OnCheckSBACollision.call(a1, a2) };
+        let mut timer = self.tx.timer();
+
+        let ret = timer.exclude(|| unsafe { OnCheckSBACollision.call(a1, a2) });
 
         if ret != 0 {
             let entity_ptr = unsafe { a1.byte_add(0x10).read() } as *const usize;
@@ -209,7 +214,7 @@ impl OnCheckSBACollisionHook {
                 actor_index: source_parent_idx,
             });
 
-            let _ = self.tx.send(payload);
+            let _ = timer.send(payload);
         }
 
         ret
@@ -250,13 +255,15 @@ impl OnContinueSBAChainHook {
     }
 
     fn run(&self, player_entity: *const usize, a2: *const usize) -> usize {
+        let mut timer = self.tx.timer();
+
         #[cfg(feature = "console")]
         println!(
             "on continue sba chain: player_entity={:p}, a2={:p}",
             player_entity, a2
         );
 
-        let ret = unsafe { OnContinueSBAChain.call(player_entity, a2) };
+        let ret = timer.exclude(|| unsafe { OnContinueSBAChain.call(player_entity, a2) });
 
         let source_idx = actor_idx(player_entity);
         let source_type_id = actor_type_id(player_entity);
@@ -267,7 +274,7 @@ impl OnContinueSBAChainHook {
             actor_index: source_parent_idx,
         });
 
-        let _ = self.tx.send(payload);
+        let _ = timer.send(payload);
 
         ret
     }
@@ -307,12 +314,14 @@ impl OnRemoteSBAUpdateHook {
     }
 
     fn run(&self, player_entity: *const usize, a2: *const usize, a3: f32, a4: f32) -> usize {
+        let mut timer = self.tx.timer();
+
         let sba_offset = SBA_OFFSET.load(Ordering::Relaxed);
         let sba_value_ptr =
             unsafe { player_entity.byte_add(sba_offset as usize).byte_add(0x7C) } as *const f32;
         let old_sba_value = unsafe { sba_value_ptr.read() };
 
-        let ret = unsafe { OnRemoteSBAUpdate.call(player_entity, a2, a3, a4) };
+        let ret = timer.exclude(|| unsafe { OnRemoteSBAUpdate.call(player_entity, a2, a3, a4) });
 
         let source_idx = actor_idx(player_entity);
         let source_type_id = actor_type_id(player_entity);
@@ -331,7 +340,7 @@ impl OnRemoteSBAUpdateHook {
                 actor_index: source_parent_idx,
             });
 
-            let _ = self.tx.send(payload);
+            let _ = timer.send(payload);
         } else {
             let payload = Message::OnUpdateSBA(protocol::OnUpdateSBAEvent {
                 actor_index: source_parent_idx,
@@ -339,7 +348,7 @@ impl OnRemoteSBAUpdateHook {
                 sba_added,
             });
 
-            let _ = self.tx.send(payload);
+            let _ = timer.send(payload);
         }
 
         ret
@@ -1,91 +1,243 @@
-use std::io::Write;
+use std::io::{BufWriter, Write};
 use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread::Thread;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
-use futures::sink::SinkExt;
-use interprocess::os::windows::named_pipe::tokio::{PipeListenerOptionsExt, SendPipeStream};
-use interprocess::os::windows::named_pipe::{pipe_mode, PipeListenerOptions, PipeMode};
+use interprocess::os::windows::named_pipe::{
+    pipe_mode, PipeListenerOptions, PipeMode, SendPipeStream,
+};
 use log::{info, warn};
-use tokio::sync::broadcast;
 
+mod data;
 mod event;
 mod hooks;
+mod metrics;
 mod process;
 
-use protocol::Message;
-use tokio_util::codec::{FramedWrite, LengthDelimitedCodec};
+use protocol::{
+    Frame, HookInstallStatus, HookState, HookStatusEvent, Message, MessagesDroppedEvent,
+};
 
-async fn handle_client(
-    mut stream: FramedWrite<SendPipeStream<pipe_mode::Bytes>, LengthDelimitedCodec>,
-    mut rx: event::Rx,
-) -> Result<()> {
-    while let Ok(msg) = rx.recv().await {
-        let bytes = protocol::bincode::serialize(&msg)?;
-        stream.send(bytes.into()).await?;
+/// How often the detour overhead measurements are logged.
+const METRICS_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A connected client, frames are buffered and flushed after each batch.
+struct Client {
+    stream: BufWriter<SendPipeStream<pipe_mode::Bytes>>,
+}
+
+impl Client {
+    /// Writes a length-delimited frame, matching the parser's `LengthDelimitedCodec`.
+    fn write_frame(&mut self, frame: &[u8]) -> std::io::Result<()> {
+        self.stream.write_all(&(frame.len() as u32).to_be_bytes())?;
+        self.stream.write_all(frame)
     }
 
-    Ok(())
+    /// Writes a message that isn't part of the numbered event stream.
+    fn write_message(&mut self, message: Message) -> Result<()> {
+        let frame = protocol::bincode::serialize(&Frame {
+            sequence: None,
+            message,
+        })?;
+
+        Ok(self.write_frame(&frame)?)
+    }
+
+    fn write_replay(&mut self, replay: Vec<Message>) -> Result<()> {
+        for message in replay {
+            self.write_message(message)?;
+        }
+
+        Ok(self.stream.flush()?)
+    }
 }
 
-#[derive(Debug)]
 struct Server {
-    tx: event::Tx,
+    rx: event::Rx,
+    /// Result of installing the hooks, sent to every client when it connects.
+    hook_status: HookStatusEvent,
+    clients: Vec<Client>,
+    /// Sequence number of the next frame.
+    sequence: u64,
+    total_dropped: u64,
 }
 
 impl Server {
-    fn new() -> Self {
-        let (tx, _) = broadcast::channel::<Message>(1024);
-        Server { tx }
+    fn new(rx: event::Rx, hook_status: HookStatusEvent) -> Self {
+        Server {
+            rx,
+            hook_status,
+            clients: Vec::new(),
+            sequence: 0,
+            total_dropped: 0,
+        }
     }
 
-    async fn run(&self) {
-        if let Ok(listener) = PipeListenerOptions::new()
-            .path(protocol::PIPE_NAME)
-            .mode(PipeMode::Bytes)
-            .accept_remote(false)
-            .create_tokio_send_only()
-        {
-            loop {
-                let read_pipe = listener.accept().await;
-                match read_pipe {
-                    Ok(stream) => {
-                        let rx = self.tx.subscribe();
-                        tokio::spawn(async move {
-                            let encoder = LengthDelimitedCodec::new();
-                            let writer = FramedWrite::new(stream, encoder);
-
-                            let _ = handle_client(writer, rx).await;
-                        });
-                    }
-                    Err(e) => {
-                        warn!("Error accepting client: {:?}", e);
-                    }
-                }
+    /// Catches a new client up with the current state: which hooks are active, the quest that was loaded,
+    /// the current area, and the players that were already loaded.
+    fn add_client(&mut self, stream: SendPipeStream<pipe_mode::Bytes>) {
+        let mut client = Client {
+            stream: BufWriter::new(stream),
+        };
+
+        let mut replay = vec![Message::HookStatus(self.hook_status.clone())];
+
+        // Sent in the same order as the game, where the quest is loaded before any of its areas are entered.
+        replay.extend(self.rx.quest_start().map(Message::OnQuestStart));
+        replay.push(Message::OnAreaEnter(hooks::current_area_event()));
+
+        replay.extend(self.rx.players().into_iter().map(Message::PlayerLoadEvent));
+
+        match client.write_replay(replay) {
+            Ok(_) => self.clients.push(client),
+            Err(e) => warn!("Could not send initial state to client: {:?}", e),
+        }
+    }
+
+    /// Sends everything that was queued by the detours, returns false if there was nothing to send.
+    fn send_pending(&mut self) -> bool {
+        let dropped = self.rx.take_dropped();
+
+        if dropped > 0 {
+            // Skip the sequence numbers of the dropped messages, so the parser sees a gap.
+            self.sequence += dropped;
+            self.total_dropped += dropped;
+
+            warn!(
+                "Writer lagged behind, dropped {} messages ({} total)",
+                dropped, self.total_dropped
+            );
+
+            let event = MessagesDroppedEvent {
+                dropped,
+                total_dropped: self.total_dropped,
+            };
+
+            self.clients.retain_mut(|client| {
+                client
+                    .write_message(Message::MessagesDropped(event.clone()))
+                    .is_ok()
+            });
+        }
+
+        let mut sent = false;
+
+        while let Some(message) = self.rx.pop() {
+            let sequence = self.sequence;
+            self.sequence += 1;
+            sent = true;
+
+            if self.clients.is_empty() {
+                continue;
+            }
+
+            match Frame::encode(Some(sequence), &message) {
+                Ok(frame) => self
+                    .clients
+                    .retain_mut(|client| client.write_frame(&frame).is_ok()),
+                Err(e) => warn!("Could not encode frame: {:?}", e),
+            }
+        }
+
+        if sent || dropped > 0 {
+            self.clients
+                .retain_mut(|client| client.stream.flush().is_ok());
+        }
+
+        sent
+    }
+
+    /// Runs the writer loop on the current thread, clients are accepted on a separate thread.
+    fn run(mut self) {
+        let (client_tx, client_rx) = mpsc::channel();
+        let writer = std::thread::current();
+
+        self.rx.register_writer();
+        std::thread::spawn(move || accept_clients(client_tx, writer));
+
+        let mut last_metrics_report = Instant::now();
+
+        loop {
+            while let Ok(stream) = client_rx.try_recv() {
+                self.add_client(stream);
+            }
+
+            if last_metrics_report.elapsed() >= METRICS_INTERVAL {
+                self.rx.metrics().report();
+                last_metrics_report = Instant::now();
+            }
+
+            if !self.send_pending() {
+                self.rx
+                    .wait(METRICS_INTERVAL.saturating_sub(last_metrics_report.elapsed()));
             }
         }
     }
 }
 
-#[tokio::main]
-async fn setup() {
-    info!("Setting up named pipe listener");
+/// Accepts pipe clients, handing them to the writer thread and waking it up.
+fn accept_clients(client_tx: mpsc::Sender<SendPipeStream<pipe_mode::Bytes>>, writer: Thread) {
+    let listener = match PipeListenerOptions::new()
+        .path(protocol::PIPE_NAME)
+        .mode(PipeMode::Bytes)
+        .accept_remote(false)
+        .create_send_only::<pipe_mode::Bytes>()
+    {
+        Ok(listener) => listener,
+        Err(e) => {
+            warn!("Could not create named pipe listener: {:?}", e);
+            return;
+        }
+    };
 
-    let server = Server::new();
-    let tx = server.tx.clone();
+    loop {
+        match listener.accept() {
+            Ok(stream) => {
+                if client_tx.send(stream).is_err() {
+                    return;
+                }
+
+                writer.unpark();
+            }
+            Err(e) => {
+                warn!("Error accepting client: {:?}", e);
+            }
+        }
+    }
+}
+
+fn setup() {
+    let (tx, rx) = event::channel();
 
     info!("Setting up hooks...");
 
-    match hooks::setup_hooks(tx) {
-        Ok(_) => info!("Hooks initialized"),
-        Err(e) => warn!("Error initializing hooks: {:?}", e),
-    }
+    let hook_status = match hooks::setup_hooks(tx) {
+        Ok(hook_status) => {
+            info!("Hooks initialized, {} failed", hook_status.failed().count());
+            hook_status
+        }
+        Err(e) => {
+            warn!("Error initializing hooks: {:?}", e);
+            HookStatusEvent {
+                hooks: vec![HookInstallStatus {
+                    name: "process".to_string(),
+                    state: HookState::Failed,
+                    error: Some(format!("{:#}", e)),
+                }],
+            }
+        }
+    };
 
     #[cfg(feature = "console")]
     println!("Hook library initialized");
 
     let _ = std::io::stdout().flush();
 
-    server.run().await;
+    info!("Setting up named pipe listener");
+
+    Server::new(rx, hook_status).run();
 }
 
 fn initialize_logger() -> anyhow::Result<()> {
@@ -0,0 +1,113 @@
+/*!
+Measures how long the detours spend on their own work, which is time added to the game's frame.
+Timings are grouped by the type of message each detour sends, and periodically written to `gbfr-logs.txt` by the
+writer thread.
+*/
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use log::info;
+use protocol::Message;
+
+const KINDS: [&str; 15] = [
+    "damage",
+    "area_enter",
+    "quest_start",
+    "quest_complete",
+    "sba_update",
+    "sba_attempt",
+    "sba_perform",
+    "sba_chain",
+    "player_load",
+    "death",
+    "enemy_death",
+    "enemy_condition",
+    "status_change",
+    "skill_cast",
+    "other",
+];
+
+/// Index of the message's type in `KINDS`. There is no catch-all, so that new messages have to be added here.
+fn kind_index(message: &Message) -> usize {
+    match message {
+        Message::DamageEvent(_) => 0,
+        Message::OnAreaEnter(_) => 1,
+        Message::OnQuestStart(_) => 2,
+        Message::OnQuestComplete(_) => 3,
+        Message::OnUpdateSBA(_) => 4,
+        Message::OnAttemptSBA(_) => 5,
+        Message::OnPerformSBA(_) => 6,
+        Message::OnContinueSBAChain(_) => 7,
+        Message::PlayerLoadEvent(_) => 8,
+        Message::OnDeathEvent(_) => 9,
+        Message::OnEnemyDeath(_) => 10,
+        Message::OnEnemyCondition(_) => 11,
+        Message::OnStatusChange(_) => 12,
+        Message::OnSkillCast(_) => 13,
+        Message::HookStatus(_) | Message::MessagesDropped(_) | Message::Unknown { .. } => 14,
+    }
+}
+
+#[derive(Default)]
+struct Counter {
+    calls: AtomicU64,
+    total_nanos: AtomicU64,
+    max_nanos: AtomicU64,
+}
+
+#[derive(Default)]
+pub struct Metrics {
+    counters: [Counter; KINDS.len()],
+    /// Total calls when the metrics were last reported, to skip reporting when nothing happened.
+    last_reported_calls: AtomicU64,
+}
+
+impl Metrics {
+    pub fn record(&self, message: &Message, elapsed: Duration) {
+        let counter = &self.counters[kind_index(message)];
+        let nanos = elapsed.as_nanos() as u64;
+
+        counter.calls.fetch_add(1, Ordering::Relaxed);
+        counter.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+        counter.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+    }
+
+    /// Logs the average and maximum detour time for each message type, if anything was sent since the last report.
+    pub fn report(&self) {
+        let total_calls: u64 = self
+            .counters
+            .iter()
+            .map(|counter| counter.calls.load(Ordering::Relaxed))
+            .sum();
+
+        if self
+            .last_reported_calls
+            .swap(total_calls, Ordering::Relaxed)
+            == total_calls
+        {
+            return;
+        }
+
+        for (name, counter) in KINDS.iter().zip(self.counters.iter()) {
+            let calls = counter.calls.load(Ordering::Relaxed);
+
+            if calls == 0 {
+                continue;
+            }
+
+            let average = counter.total_nanos.load(Ordering::Relaxed) / calls;
+            let max = counter.max_nanos.load(Ordering::Relaxed);
+
+            info!(
+                "Detour time for {}: {} calls, avg {:.2}us, max {:.2}us",
+                name,
+                calls,
+                average as f64 / 1000.0,
+                max as f64 / 1000.0
+            );
+        }
+    }
+}
@@ -1,8 +1,8 @@
+use std::sync::OnceLock;
+
 use anyhow::anyhow;
-use pelite::{
-    pattern,
-    pe64::{Pe, PeView},
-};
+use pelite::pe64::PeView;
+use signatures::{SignatureDatabase, SignatureMatch};
 use thiserror::Error;
 use windows::Win32::Foundation::HMODULE;
 use windows::Win32::System::Diagnostics::ToolHelp::{
@@ -10,6 +10,22 @@ use windows::Win32::System::Diagnostics::ToolHelp::{
     PROCESSENTRY32W, TH32CS_SNAPMODULE, TH32CS_SNAPMODULE32, TH32CS_SNAPPROCESS,
 };
 
+use crate::data;
+
+static SIGNATURES: OnceLock<SignatureDatabase> = OnceLock::new();
+
+/// Loads the signature database, preferring `%APPDATA%\gbfr-logs\signatures.json` if it is valid.
+pub fn setup_signatures() {
+    let database = data::load_override("signatures.json", SignatureDatabase::from_json)
+        .unwrap_or_else(SignatureDatabase::embedded);
+
+    let _ = SIGNATURES.set(database);
+}
+
+fn signatures() -> &'static SignatureDatabase {
+    SIGNATURES.get_or_init(SignatureDatabase::embedded)
+}
+
 #[derive(Error, Debug)]
 pub enum ProcessError {
     #[error("Process was not found with that name")]
@@ -86,47 +102,29 @@ impl Process {
         found_process.ok_or(ProcessError::ProcessNotFound)
     }
 
-    /// Searches and returns the RVAs of the function that matches the given signature pattern.
-    pub fn search_address(&self, signature_pattern: &str) -> anyhow::Result<usize> {
-        let view = unsafe { PeView::module(self.module_handle.0 as *const u8) };
-        let scanner = view.scanner();
-        let pattern = pattern::parse(signature_pattern)?;
-
-        let mut addrs = [0; 8];
-
-        let mut matches = scanner.matches_code(&pattern);
-
-        let mut first_addr = None;
-
-        // addrs[0] = RVA of where the match was found.
-        // addrs[1] = RVA of the function being called.
-        while matches.next(&mut addrs) {
-            first_addr = Some(self.base_address + addrs[1] as usize);
-        }
+    /// Searches and returns the address of the function that matches the named signature.
+    /// Each of the signature's patterns are tried in order until one matches.
+    pub fn search_address(&self, signature_name: &str) -> anyhow::Result<usize> {
+        let found = self.find_signature(signature_name)?;
+        Ok(self.base_address + found.target as usize)
+    }
 
-        first_addr.ok_or(anyhow!(
-            "Could not find match for pattern: {}",
-            signature_pattern
-        ))
+    /// Searches and returns the value of the type `T` that matches the named signature.
+    /// Each of the signature's patterns are tried in order until one matches.
+    pub fn search_slice<T>(&self, signature_name: &str) -> anyhow::Result<T> {
+        let found = self.find_signature(signature_name)?;
+        let addr = self.base_address + found.target as usize;
+        let ptr = addr as *const T;
+        Ok(unsafe { ptr.read_unaligned() })
     }
 
-    /// Searches and returns the value of the type `T` that matches the given signature pattern.
-    pub fn search_slice<T>(&self, signature_pattern: &str) -> anyhow::Result<T> {
+    fn find_signature(&self, signature_name: &str) -> anyhow::Result<SignatureMatch> {
         let view = unsafe { PeView::module(self.module_handle.0 as *const u8) };
-        let scanner = view.scanner();
-        let pattern = pattern::parse(signature_pattern)?;
-        let mut addrs = [0; 8];
-        let matches = scanner.matches_code(&pattern).next(&mut addrs);
-
-        if matches {
-            let addr = self.base_address + addrs[1] as usize;
-            let ptr = addr as *const T;
-            Ok(unsafe { ptr.read_unaligned() })
-        } else {
-            Err(anyhow!(
-                "Could not find match for pattern: {}",
-                signature_pattern
-            ))
-        }
+        let signature = signatures().get(signature_name)?;
+
+        signature.find(view)?.map(|(_, found)| found).ok_or(anyhow!(
+            "Could not find match for signature: {}",
+            signature_name
+        ))
     }
 }
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use rusqlite::Connection;
+use rusqlite::{params, Connection};
 use sea_query::{Expr, Condition, Iden, Order, Query, SqliteQueryBuilder};
 use sea_query_rusqlite::RusqliteBinder;
 use serde::Serialize;
@@ -37,6 +37,7 @@ enum Logs {
     QuestId,
     QuestElapsedTime,
     QuestCompleted,
+    QuestOutcome,
 }
 
 #[derive(Debug, Serialize)]
@@ -76,6 +77,8 @@ pub struct LogEntry {
     quest_elapsed_time: Option<u32>,
     /// Was quest completed?
     quest_completed: Option<bool>,
+    /// How the quest ended: Completed or Unknown.
+    quest_outcome: Option<String>,
 }
 
 pub fn get_logs(
@@ -121,6 +124,7 @@ pub fn get_logs(
             Logs::QuestId,
             Logs::QuestElapsedTime,
             Logs::QuestCompleted,
+            Logs::QuestOutcome,
         ])
         .conditions(
             filter_by_enemy_id.is_some(),
@@ -222,6 +226,7 @@ pub fn get_logs(
                 quest_id: row.get(14)?,
                 quest_elapsed_time: row.get(15)?,
                 quest_completed: row.get(16)?,
+                quest_outcome: row.get(17)?,
             })
         })
         .collect::<rusqlite::Result<Vec<LogEntry>>>();
@@ -320,3 +325,132 @@ pub fn get_logs_count(
 
     Ok(row)
 }
+
+/// A player's loadout in one log.
+pub struct LoadoutEntry {
+    pub log_id: u64,
+    /// Milliseconds since UNIX epoch.
+    pub time: i64,
+    pub fingerprint: String,
+    pub dps: Option<f64>,
+}
+
+/// Loadouts a player used on a character, oldest first. Logs saved before loadouts were recorded are skipped.
+pub fn get_loadouts(
+    conn: &Connection,
+    player_id: &str,
+    player_character: &str,
+) -> Result<Vec<LoadoutEntry>> {
+    let sql = (1..=4)
+        .map(|slot| {
+            format!(
+                "SELECT id, time, p{slot}_loadout, p{slot}_dps FROM logs \
+                WHERE p{slot}_name = ?1 AND p{slot}_type = ?2 AND p{slot}_loadout IS NOT NULL"
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(" UNION ALL ");
+
+    let mut stmt = conn.prepare(&format!("{sql} ORDER BY time ASC"))?;
+
+    let rows = stmt
+        .query_map([player_id, player_character], |row| {
+            Ok(LoadoutEntry {
+                log_id: row.get(0)?,
+                time: row.get(1)?,
+                fingerprint: row.get(2)?,
+                dps: row.get(3)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<LoadoutEntry>>>()?;
+
+    Ok(rows)
+}
+
+/// Logs saved before loadouts were recorded that have players whose loadout can still be read from the log itself.
+pub fn get_logs_without_loadouts(conn: &Connection) -> Result<Vec<u64>> {
+    let mut stmt = conn.prepare(
+        "SELECT id FROM logs \
+        WHERE version = 1 \
+        AND p1_loadout IS NULL AND p2_loadout IS NULL AND p3_loadout IS NULL AND p4_loadout IS NULL \
+        AND (p1_name IS NOT NULL OR p2_name IS NOT NULL OR p3_name IS NOT NULL OR p4_name IS NOT NULL)",
+    )?;
+
+    let rows = stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<rusqlite::Result<Vec<u64>>>()?;
+
+    Ok(rows)
+}
+
+/// Stores the loadout fingerprint and DPS of the player in each party slot of a log.
+pub fn update_loadouts(conn: &Connection, id: u64, loadouts: [(Option<String>, Option<f64>); 4]) -> Result<()> {
+    let [(p1_loadout, p1_dps), (p2_loadout, p2_dps), (p3_loadout, p3_dps), (p4_loadout, p4_dps)] = loadouts;
+
+    conn.execute(
+        "UPDATE logs SET \
+        p1_loadout = ?, p1_dps = ?, p2_loadout = ?, p2_dps = ?, \
+        p3_loadout = ?, p3_dps = ?, p4_loadout = ?, p4_dps = ? \
+        WHERE id = ?",
+        params![p1_loadout, p1_dps, p2_loadout, p2_dps, p3_loadout, p3_dps, p4_loadout, p4_dps, id],
+    )?;
+
+    Ok(())
+}
+
+/// A distinct loadout a player used, and how they did with it.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadoutSummary {
+    pub fingerprint: String,
+    /// Milliseconds since UNIX epoch of the first log with this loadout.
+    pub first_seen: i64,
+    /// Milliseconds since UNIX epoch of the last log with this loadout.
+    pub last_seen: i64,
+    /// Logs with this loadout, oldest first.
+    pub log_ids: Vec<u64>,
+    pub best_dps: Option<f64>,
+    pub average_dps: Option<f64>,
+}
+
+/// Groups loadouts by fingerprint, in the order they were first used.
+pub fn summarize_loadouts(entries: Vec<LoadoutEntry>) -> Vec<LoadoutSummary> {
+    let mut summaries: Vec<(LoadoutSummary, Vec<f64>)> = Vec::new();
+
+    for entry in entries {
+        let index = match summaries.iter().position(|(summary, _)| summary.fingerprint == entry.fingerprint) {
+            Some(index) => index,
+            None => {
+                summaries.push((
+                    LoadoutSummary {
+                        fingerprint: entry.fingerprint,
+                        first_seen: entry.time,
+                        last_seen: entry.time,
+                        log_ids: Vec::new(),
+                        best_dps: None,
+                        average_dps: None,
+                    },
+                    Vec::new(),
+                ));
+                summaries.len() - 1
+            }
+        };
+
+        let (summary, dps) = &mut summaries[index];
+        summary.last_seen = entry.time;
+        summary.log_ids.push(entry.log_id);
+        dps.extend(entry.dps);
+    }
+
+    summaries
+        .into_iter()
+        .map(|(mut summary, dps)| {
+            if !dps.is_empty() {
+                summary.best_dps = dps.iter().copied().reduce(f64::max);
+                summary.average_dps = Some(dps.iter().sum::<f64>() / dps.len() as f64);
+            }
+
+            summary
+        })
+        .collect()
+}
@@ -36,6 +36,16 @@ pub fn setup_db() -> Result<()> {
         M::up("ALTER TABLE logs ADD COLUMN quest_id INTEGER"),
         M::up("ALTER TABLE logs ADD COLUMN quest_elapsed_time INTEGER"),
         M::up("ALTER TABLE logs ADD COLUMN quest_completed BOOLEAN"),
+        M::up("ALTER TABLE logs ADD COLUMN quest_outcome TEXT"),
+        M::up("UPDATE logs SET quest_outcome = 'Completed' WHERE quest_completed = 1"),
+        M::up("ALTER TABLE logs ADD COLUMN p1_loadout TEXT"),
+        M::up("ALTER TABLE logs ADD COLUMN p1_dps REAL"),
+        M::up("ALTER TABLE logs ADD COLUMN p2_loadout TEXT"),
+        M::up("ALTER TABLE logs ADD COLUMN p2_dps REAL"),
+        M::up("ALTER TABLE logs ADD COLUMN p3_loadout TEXT"),
+        M::up("ALTER TABLE logs ADD COLUMN p3_dps REAL"),
+        M::up("ALTER TABLE logs ADD COLUMN p4_loadout TEXT"),
+        M::up("ALTER TABLE logs ADD COLUMN p4_dps REAL"),
     ]);
 
     info!("Database found, running migrations..");
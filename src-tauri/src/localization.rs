@@ -0,0 +1,274 @@
+/*!
+Localized names for exports, read from the same translation files in `lang/<locale>` as the frontend.
+
+Keys are looked up the same way the frontend does with i18next (`namespace:path.to.key`), so names in exports match
+what is shown in the app. Missing translations fall back to the base language of the locale and then to English.
+*/
+
+use std::{collections::HashMap, path::Path};
+
+use log::warn;
+use protocol::ActionType;
+use serde_json::Value;
+
+use crate::parser::{
+    constants::{CharacterType, EnemyType},
+    equipment,
+};
+
+/// Namespaces that are loaded for each locale, skill names are part of `ui`.
+const NAMESPACES: [&str; 9] = [
+    "ui",
+    "characters",
+    "enemies",
+    "quests",
+    "sigils",
+    "traits",
+    "weapons",
+    "overmasteries",
+    "items",
+];
+
+#[derive(Debug, Default)]
+pub struct Localizer {
+    /// Translations of each locale to try in order, keyed by `namespace:key`.
+    translations: Vec<HashMap<String, String>>,
+}
+
+impl Localizer {
+    /// Loads the translations for the locale from the `lang` directory, skipping files that are missing.
+    pub fn load(lang_dir: &Path, locale: &str) -> Self {
+        let translations = fallback_locales(locale)
+            .into_iter()
+            .map(|locale| {
+                let mut translations = HashMap::new();
+
+                for namespace in NAMESPACES {
+                    let path = lang_dir.join(&locale).join(format!("{namespace}.json"));
+
+                    let Ok(json) = std::fs::read_to_string(&path) else {
+                        continue;
+                    };
+
+                    if let Err(e) = add_namespace(&mut translations, namespace, &json) {
+                        warn!("Could not load translations from {:?}: {:?}", path, e);
+                    }
+                }
+
+                translations
+            })
+            .collect();
+
+        Self { translations }
+    }
+
+    /// Returns the translation of the first key that has one, like `t([...])` in the frontend.
+    fn t(&self, keys: &[String]) -> Option<&str> {
+        keys.iter().find_map(|key| {
+            self.translations
+                .iter()
+                .find_map(|translations| translations.get(key))
+                .map(String::as_str)
+        })
+    }
+
+    pub fn character(&self, character_type: CharacterType) -> String {
+        self.t(&[
+            format!("characters:{character_type}"),
+            format!("ui:characters.{character_type}"),
+        ])
+        .map(str::to_string)
+        .unwrap_or_else(|| character_type.to_string())
+    }
+
+    /// Name of an enemy, falling back to the English name compiled into the app if none were loaded.
+    pub fn enemy(&self, enemy_type: EnemyType) -> String {
+        let hash = format!("{:08x}", enemy_type.hash());
+
+        self.t(&[format!("enemies:{hash}.text")])
+            .or_else(|| enemy_type.info().and_then(|info| info.name("en")))
+            .map(str::to_string)
+            .unwrap_or(hash)
+    }
+
+    /// Name of a trait, falling back to the English name compiled into the app if none were loaded.
+    pub fn trait_name(&self, trait_id: u32) -> String {
+        let hash = format!("{trait_id:08x}");
+
+        self.t(&[format!("traits:{hash}.text")])
+            .or_else(|| {
+                equipment::catalog()
+                    .trait_info(trait_id)
+                    .map(|info| info.name.as_str())
+            })
+            .map(str::to_string)
+            .unwrap_or(hash)
+    }
+
+    /// Name of a skill used by a character, or by one of its children (e.g. Ferry's ghosts).
+    pub fn skill(
+        &self,
+        character_type: CharacterType,
+        child_character_type: CharacterType,
+        action_type: ActionType,
+    ) -> String {
+        let keys = match action_type {
+            ActionType::LinkAttack => vec![
+                format!("ui:skills.{character_type}.link-attack"),
+                "ui:skills.default.link-attack".to_string(),
+            ],
+            ActionType::SBA => vec![
+                format!("ui:skills.{character_type}.skybound-arts"),
+                "ui:skills.default.skybound-arts".to_string(),
+            ],
+            ActionType::SupplementaryDamage(_) => {
+                vec!["ui:skills.default.supplementary-damage".to_string()]
+            }
+            ActionType::DamageOverTime(_) => vec![
+                format!("ui:skills.{child_character_type}.damage-over-time"),
+                format!("ui:skills.{character_type}.damage-over-time"),
+                "ui:skills.default.damage-over-time".to_string(),
+            ],
+            ActionType::Normal(id) => vec![
+                format!("ui:skills.{child_character_type}.{id}"),
+                format!("ui:skills.{character_type}.{id}"),
+                format!("ui:skills.default.{id}"),
+            ],
+        };
+
+        self.t(&keys)
+            .map(str::to_string)
+            .unwrap_or_else(|| action_type.to_string())
+    }
+}
+
+/// Locales to look translations up in, e.g. `zh-TW` falls back to `zh-CN` and `ko-KR` to `ko`.
+fn fallback_locales(locale: &str) -> Vec<String> {
+    let mut locales = vec![locale.to_string()];
+
+    if let Some((language, _)) = locale.split_once('-') {
+        locales.push(language.to_string());
+    }
+
+    if locale == "zh-TW" {
+        locales.push("zh-CN".to_string());
+    }
+
+    locales.push("en".to_string());
+    locales.dedup();
+    locales
+}
+
+/// Flattens a translation file into `namespace:path.to.key` entries.
+fn add_namespace(
+    translations: &mut HashMap<String, String>,
+    namespace: &str,
+    json: &str,
+) -> serde_json::Result<()> {
+    fn flatten(translations: &mut HashMap<String, String>, prefix: String, value: Value) {
+        match value {
+            Value::String(text) => {
+                translations.insert(prefix, text);
+            }
+            Value::Object(entries) => {
+                for (key, value) in entries {
+                    flatten(translations, format!("{prefix}.{key}"), value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let Value::Object(entries) = serde_json::from_str(json)? else {
+        return Ok(());
+    };
+
+    for (key, value) in entries {
+        flatten(translations, format!("{namespace}:{key}"), value);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn localizer() -> Localizer {
+        let mut jp = HashMap::new();
+        let mut en = HashMap::new();
+
+        add_namespace(&mut jp, "characters", r#"{ "Pl0000": "グラン" }"#).unwrap();
+        add_namespace(
+            &mut en,
+            "ui",
+            r#"{
+                "characters": { "Pl0100": "Djeeta" },
+                "skills": {
+                    "default": { "link-attack": "Link Attack" },
+                    "Pl0000": { "1201": "Decimate (Arts I)" }
+                }
+            }"#,
+        )
+        .unwrap();
+        add_namespace(
+            &mut en,
+            "enemies",
+            r#"{ "427e32fe": { "key": "EM0000", "text": "Goblin Stormer" } }"#,
+        )
+        .unwrap();
+
+        Localizer {
+            translations: vec![jp, en],
+        }
+    }
+
+    #[test]
+    fn names_fall_back_to_english_then_raw_names() {
+        let localizer = localizer();
+
+        assert_eq!(localizer.character(CharacterType::Pl0000), "グラン");
+        assert_eq!(localizer.character(CharacterType::Pl0100), "Djeeta");
+        assert_eq!(localizer.character(CharacterType::Pl0200), "Pl0200");
+        assert_eq!(
+            localizer.enemy(EnemyType::from_hash(0x427E32FE)),
+            "Goblin Stormer"
+        );
+        assert_eq!(
+            localizer.enemy(EnemyType::from_hash(0x2B31654B)),
+            "Lucilius"
+        );
+        assert_eq!(localizer.enemy(EnemyType::from_hash(0x1234)), "00001234");
+        assert_eq!(localizer.trait_name(0xDC584F60), "DMG Cap");
+        assert_eq!(localizer.trait_name(0x1234), "00001234");
+    }
+
+    #[test]
+    fn skills_are_looked_up_like_the_frontend() {
+        let localizer = localizer();
+        let gran = CharacterType::Pl0000;
+
+        assert_eq!(
+            localizer.skill(gran, gran, ActionType::Normal(1201)),
+            "Decimate (Arts I)"
+        );
+        assert_eq!(
+            localizer.skill(gran, gran, ActionType::LinkAttack),
+            "Link Attack"
+        );
+        assert_eq!(
+            localizer.skill(gran, gran, ActionType::Normal(9999)),
+            "Skill (9999)"
+        );
+    }
+
+    #[test]
+    fn locales_fall_back_to_their_language_and_english() {
+        assert_eq!(fallback_locales("ko-KR"), vec!["ko-KR", "ko", "en"]);
+        assert_eq!(
+            fallback_locales("zh-TW"),
+            vec!["zh-TW", "zh", "zh-CN", "en"]
+        );
+        assert_eq!(fallback_locales("en"), vec!["en"]);
+    }
+}
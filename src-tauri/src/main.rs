@@ -6,16 +6,21 @@ use std::{
     fs::File,
     io::Write,
     path::Path,
-    sync::atomic::{AtomicBool, Ordering},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        RwLock,
+    },
 };
 
 use anyhow::Context;
 use db::logs::LogEntry;
 use dll_syringe::{process::OwnedProcess, Syringe};
 use interprocess::os::windows::named_pipe::tokio::RecvPipeStream;
-use log::{info, LevelFilter};
+use localization::Localizer;
+use log::{info, warn, LevelFilter};
 use parser::{
     constants::{CharacterType, EnemyType},
+    enemies::{self, EnemyInfo},
     v1::{self, PlayerData},
 };
 use protocol::Message;
@@ -31,11 +36,14 @@ use tokio_stream::StreamExt;
 use tokio_util::codec::FramedRead;
 
 mod db;
+mod localization;
 mod parser;
 
 struct AlwaysOnTop(AtomicBool);
 struct ClickThrough(AtomicBool);
 struct DebugMode(AtomicBool);
+/// Names used in exports, in the language the app is set to.
+struct AppLocalizer(RwLock<Localizer>);
 
 #[tauri::command]
 fn set_debug_mode(app: AppHandle, state: State<DebugMode>, enabled: bool) {
@@ -50,6 +58,14 @@ fn set_debug_mode(app: AppHandle, state: State<DebugMode>, enabled: bool) {
     state.0.store(enabled, Ordering::Release);
 }
 
+#[tauri::command]
+fn set_locale(app: AppHandle, state: State<AppLocalizer>, locale: String) {
+    if let Some(lang_dir) = app.path_resolver().resolve_resource("lang") {
+        let localizer = Localizer::load(&lang_dir, &locale);
+        *state.0.write().unwrap() = localizer;
+    }
+}
+
 #[tauri::command]
 async fn delete_all_logs() -> Result<(), String> {
     let conn = db::connect_to_db().map_err(|e| e.to_string())?;
@@ -58,8 +74,21 @@ async fn delete_all_logs() -> Result<(), String> {
     Ok(())
 }
 
+/// Quotes a CSV field if it needs to be, localized names can contain commas.
+fn csv_field(value: String) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
 #[tauri::command]
-fn export_damage_log_to_file(id: u32, options: ParseOptions) -> Result<(), String> {
+fn export_damage_log_to_file(
+    localizer: State<AppLocalizer>,
+    id: u32,
+    options: ParseOptions,
+) -> Result<(), String> {
     let file_path = FileDialogBuilder::new()
         .add_filter("csv", &["csv"])
         .set_file_name(&format!("{id}_damage_log.csv"))
@@ -79,6 +108,8 @@ fn export_damage_log_to_file(id: u32, options: ParseOptions) -> Result<(), Strin
         .map_err(|e| e.to_string())?;
 
     let parser = parser::deserialize_version(&blob, version).map_err(|e| e.to_string())?;
+    let targets = options.target_filter(&parser);
+    let localizer = localizer.0.read().unwrap();
 
     let file = File::create(file_path).map_err(|e| e.to_string())?;
 
@@ -99,17 +130,21 @@ fn export_damage_log_to_file(id: u32, options: ParseOptions) -> Result<(), Strin
                 CharacterType::from_hash(damage_event.source.parent_actor_type);
             let child_character_type = CharacterType::from_hash(damage_event.source.actor_type);
 
-            if options.targets.is_empty() || options.targets.contains(&target_type) {
+            if v1::is_targeted(targets.as_deref(), &target_type) {
                 writeln!(
                     writer,
                     "{},{},{},{},{},{},{},{},{}",
                     timestamp,
-                    parent_character_type,
-                    child_character_type,
+                    csv_field(localizer.character(parent_character_type)),
+                    csv_field(localizer.character(child_character_type)),
                     damage_event.source.parent_index,
-                    target_type,
+                    csv_field(localizer.enemy(target_type)),
                     damage_event.target.parent_index,
-                    damage_event.action_id,
+                    csv_field(localizer.skill(
+                        parent_character_type,
+                        child_character_type,
+                        damage_event.action_id
+                    )),
                     damage_event.flags,
                     damage_event.damage
                 )
@@ -123,6 +158,65 @@ fn export_damage_log_to_file(id: u32, options: ParseOptions) -> Result<(), Strin
     Ok(())
 }
 
+#[tauri::command]
+fn export_trait_totals_to_file(localizer: State<AppLocalizer>, id: u32) -> Result<(), String> {
+    let file_path = FileDialogBuilder::new()
+        .add_filter("csv", &["csv"])
+        .set_file_name(&format!("{id}_trait_totals.csv"))
+        .set_title("Export Trait Totals")
+        .save_file()
+        .ok_or("No file selected!")?;
+
+    let conn = db::connect_to_db().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare("SELECT data, version FROM logs WHERE id = ?")
+        .map_err(|e| e.to_string())?;
+
+    let (blob, version): (Vec<u8>, u8) = stmt
+        .query_row([id], |row| Ok((row.get(0)?, row.get(1)?)))
+        .context("Failed to fetch log from database")
+        .map_err(|e| e.to_string())?;
+
+    let parser = parser::deserialize_version(&blob, version).map_err(|e| e.to_string())?;
+    let localizer = localizer.0.read().unwrap();
+
+    let file = File::create(file_path).map_err(|e| e.to_string())?;
+    let mut writer = std::io::BufWriter::new(file);
+
+    writeln!(
+        writer,
+        "player_name,character_type,trait,level,max_level,overflow"
+    )
+    .map_err(|e| e.to_string())?;
+
+    for player in parser.encounter.player_data.iter().flatten() {
+        for total in player.trait_totals() {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{}",
+                csv_field(player.display_name().to_string()),
+                csv_field(localizer.character(player.character_type())),
+                csv_field(localizer.trait_name(total.trait_id)),
+                total.level,
+                total
+                    .max_level
+                    .map(|max_level| max_level.to_string())
+                    .unwrap_or_default(),
+                total
+                    .overflow
+                    .map(|overflow| overflow.to_string())
+                    .unwrap_or_default()
+            )
+            .map_err(|e| e.to_string())?;
+        }
+    }
+
+    writer.flush().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct SearchResult {
@@ -287,18 +381,63 @@ struct EncounterStateResponse {
     quest_id: Option<u32>,
     quest_timer: Option<u32>,
     quest_completed: bool,
+    quest_outcome: v1::QuestOutcome,
+    quest_difficulty: Option<u32>,
+    /// Number of events lost while recording, the log is incomplete if non-zero.
+    missing_events: u64,
     targets: Vec<EnemyType>,
     dps_chart: HashMap<u32, Vec<i32>>,
     sba_chart: HashMap<u32, Vec<f32>>,
     sba_events: Vec<(i64, protocol::Message)>,
+    /// Skybound Art usage of each player.
+    sba_stats: Vec<v1::PlayerSba>,
+    /// Link attacks on the selected targets, relative to the start of the encounter.
+    link_attacks: v1::LinkAttackStats,
     death_events: Vec<(i64, protocol::Message)>,
+    /// Who was in the party and when, relative to the start of the encounter.
+    roster: Vec<v1::RosterEntry>,
+    /// Stats or gear that changed when a player was loaded again, relative to the start of the encounter.
+    stat_changes: Vec<v1::PlayerStatChange>,
     chart_len: usize,
     sba_chart_len: usize,
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct ParseOptions {
     targets: Vec<EnemyType>,
+    /// Only count bosses, used when no targets are selected.
+    #[serde(default)]
+    bosses_only: bool,
+}
+
+impl ParseOptions {
+    /// Targets to filter the encounter by, `None` means every target.
+    ///
+    /// Only counting bosses in an encounter without any gives an empty list, which matches nothing.
+    fn target_filter(&self, parser: &v1::Parser) -> Option<Vec<EnemyType>> {
+        if !self.targets.is_empty() {
+            return Some(self.targets.clone());
+        }
+
+        if !self.bosses_only {
+            return None;
+        }
+
+        let mut bosses = Vec::new();
+
+        for (_, event) in parser.encounter.event_log() {
+            if let Message::DamageEvent(damage_event) = event {
+                let target_type = EnemyType::from_hash(damage_event.target.parent_actor_type);
+
+                if target_type.is_boss() && !bosses.contains(&target_type) {
+                    bosses.push(target_type);
+                }
+            }
+        }
+
+        Some(bosses)
+    }
 }
 
 #[tauri::command]
@@ -315,7 +454,9 @@ fn fetch_encounter_state(id: u64, options: ParseOptions) -> Result<EncounterStat
     // @TODO(false): If we deserialize from an older version, we should save it back into the DB as the newer format.
     let mut parser = parser::deserialize_version(&blob, version).map_err(|e| e.to_string())?;
 
-    parser.reparse_with_options(&options.targets);
+    let target_filter = options.target_filter(&parser);
+
+    parser.reparse_with_options(target_filter.as_deref());
 
     let duration = parser.derived_state.duration();
 
@@ -346,7 +487,7 @@ fn fetch_encounter_state(id: u64, options: ParseOptions) -> Result<EncounterStat
 
                 if let Some(chart) = player_dps.get_mut(&damage_event.source.parent_index) {
                     // Check to see if the target is in the list of targets to filter by.
-                    if options.targets.is_empty() || options.targets.contains(&target_type) {
+                    if v1::is_targeted(target_filter.as_deref(), &target_type) {
                         chart[index] += damage_event.damage;
                     }
                 }
@@ -356,6 +497,8 @@ fn fetch_encounter_state(id: u64, options: ParseOptions) -> Result<EncounterStat
     }
 
     let sba_chart = parser.generate_sba_chart(SBA_INTERVAL);
+    let sba_stats = parser.sba_stats();
+    let link_attacks = parser.link_attacks(target_filter.as_deref());
 
     let sba_events = parser
         .encounter
@@ -378,22 +521,199 @@ fn fetch_encounter_state(id: u64, options: ParseOptions) -> Result<EncounterStat
         .map(|(ts, e)| (*ts - start_time, e.clone()))
         .collect();
 
+    let stat_changes = parser
+        .encounter
+        .stat_changes()
+        .into_iter()
+        .map(|change| v1::PlayerStatChange {
+            timestamp: change.timestamp - start_time,
+            ..change
+        })
+        .collect();
+
+    let roster = parser
+        .encounter
+        .roster
+        .iter()
+        .map(|entry| v1::RosterEntry {
+            joined_at: entry.joined_at - start_time,
+            ..entry.clone()
+        })
+        .collect();
+
     Ok(EncounterStateResponse {
         encounter_state: parser.derived_state,
         players: parser.encounter.player_data,
         quest_id: parser.encounter.quest_id,
         quest_timer: parser.encounter.quest_timer,
         quest_completed: parser.encounter.quest_completed,
+        quest_outcome: parser.encounter.quest_outcome,
+        quest_difficulty: parser.encounter.quest_difficulty,
+        missing_events: parser.encounter.missing_events,
         dps_chart: player_dps,
         chart_len: (duration / DPS_INTERVAL) as usize + 1,
         sba_chart_len: (duration / SBA_INTERVAL) as usize + 1,
         sba_chart,
         sba_events,
+        sba_stats,
+        link_attacks,
         death_events,
+        roster,
+        stat_changes,
         targets,
     })
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EnemyCatalogEntry {
+    #[serde(flatten)]
+    info: EnemyInfo,
+    /// Quests the enemy was the primary target of in the saved logs.
+    quest_ids: Vec<u32>,
+}
+
+/// Every known enemy with its names, category and the quests it was fought in.
+#[tauri::command]
+fn fetch_enemy_catalog() -> Result<Vec<EnemyCatalogEntry>, String> {
+    let conn = db::connect_to_db().map_err(|e| e.to_string())?;
+
+    let mut query = conn
+        .prepare("SELECT DISTINCT primary_target, quest_id FROM logs WHERE primary_target IS NOT NULL AND quest_id IS NOT NULL")
+        .map_err(|e| e.to_string())?;
+
+    let rows = query
+        .query_map([], |row| {
+            Ok((
+                row.get::<usize, u32>(0)?, // primary_target
+                row.get::<usize, u32>(1)?, // quest_id
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut quests: HashMap<u32, Vec<u32>> = HashMap::new();
+
+    for row in rows {
+        let (primary_target, quest_id) = row.map_err(|e| e.to_string())?;
+        quests.entry(primary_target).or_default().push(quest_id);
+    }
+
+    Ok(enemies::catalog()
+        .enemies()
+        .into_iter()
+        .map(|info| {
+            let mut quest_ids = quests.remove(&info.hash).unwrap_or_default();
+            quest_ids.sort_unstable();
+
+            EnemyCatalogEntry {
+                info: info.clone(),
+                quest_ids,
+            }
+        })
+        .collect())
+}
+
+#[tauri::command]
+fn fetch_rotation(id: u64) -> Result<Vec<v1::PlayerRotation>, String> {
+    let conn = db::connect_to_db().map_err(|e| e.to_string())?;
+    let mut stmt = conn
+        .prepare("SELECT data, version FROM logs WHERE id = ?")
+        .map_err(|e| e.to_string())?;
+
+    let (blob, version): (Vec<u8>, u8) = stmt
+        .query_row([id], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| e.to_string())?;
+
+    let parser = parser::deserialize_version(&blob, version).map_err(|e| e.to_string())?;
+
+    Ok(parser.rotation())
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LoadoutHistoryEntry {
+    #[serde(flatten)]
+    summary: db::logs::LoadoutSummary,
+    /// The player as they were in the last log with this loadout, to show the gear.
+    player: Option<PlayerData>,
+}
+
+/// Distinct loadouts a player used on a character over time, with the DPS they did with each.
+#[tauri::command]
+fn fetch_loadout_history(
+    player_id: String,
+    player_character: String,
+) -> Result<Vec<LoadoutHistoryEntry>, String> {
+    let conn = db::connect_to_db().map_err(|e| e.to_string())?;
+
+    let entries =
+        db::logs::get_loadouts(&conn, &player_id, &player_character).map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare("SELECT data, version FROM logs WHERE id = ?")
+        .map_err(|e| e.to_string())?;
+
+    db::logs::summarize_loadouts(entries)
+        .into_iter()
+        .map(|summary| {
+            let Some(&id) = summary.log_ids.last() else {
+                return Ok(LoadoutHistoryEntry {
+                    summary,
+                    player: None,
+                });
+            };
+
+            let (blob, version): (Vec<u8>, u8) = stmt
+                .query_row([id], |row| Ok((row.get(0)?, row.get(1)?)))
+                .map_err(|e| e.to_string())?;
+
+            let parser = parser::deserialize_version(&blob, version).map_err(|e| e.to_string())?;
+
+            let player = parser
+                .encounter
+                .player_data
+                .into_iter()
+                .flatten()
+                .find(|player| {
+                    player.display_name() == player_id
+                        && player.character_type().to_string() == player_character
+                        && player.loadout_fingerprint() == summary.fingerprint
+                });
+
+            Ok(LoadoutHistoryEntry { summary, player })
+        })
+        .collect()
+}
+
+/// Stores the loadouts of logs saved before loadouts were recorded, read from each log's encounter.
+fn backfill_loadouts() -> anyhow::Result<()> {
+    let conn = db::connect_to_db()?;
+    let ids = db::logs::get_logs_without_loadouts(&conn)?;
+
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    info!("Backfilling loadouts of {} logs..", ids.len());
+
+    let mut stmt = conn.prepare("SELECT data, version FROM logs WHERE id = ?")?;
+
+    for id in ids {
+        let (blob, version): (Vec<u8>, u8) =
+            stmt.query_row([id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+
+        match parser::deserialize_version(&blob, version) {
+            Ok(parser) => db::logs::update_loadouts(&conn, id, parser.party_loadouts())?,
+            Err(e) => warn!(
+                "Could not read log {} to backfill its loadouts: {:?}",
+                id, e
+            ),
+        }
+    }
+
+    Ok(())
+}
+
 #[tauri::command]
 fn delete_logs(ids: Vec<u64>) -> Result<(), String> {
     let conn = db::connect_to_db().map_err(|e| e.to_string())?;
@@ -467,12 +787,16 @@ fn connect_and_run_parser(app: AppHandle) {
 
                         let debug_mode = app.state::<DebugMode>().0.load(Ordering::Relaxed);
 
-                        if let Ok(msg) = protocol::bincode::deserialize::<protocol::Message>(&msg) {
+                        if let Ok(frame) = protocol::bincode::deserialize::<protocol::Frame>(&msg) {
                             if debug_mode {
-                                let _ = logs_window.emit("debug-event", &msg);
+                                let _ = logs_window.emit("debug-event", &frame.message);
+                            }
+
+                            if let Some(sequence) = frame.sequence {
+                                state.on_sequence(sequence);
                             }
 
-                            match msg {
+                            match frame.message {
                                 protocol::Message::DamageEvent(event) => {
                                     state.on_damage_event(event);
                                 }
@@ -500,6 +824,29 @@ fn connect_and_run_parser(app: AppHandle) {
                                 protocol::Message::OnDeathEvent(event) => {
                                     state.on_death_event(event);
                                 }
+                                protocol::Message::HookStatus(event) => {
+                                    state.on_hook_status(event);
+                                }
+                                protocol::Message::MessagesDropped(event) => {
+                                    state.on_messages_dropped(event);
+                                }
+                                protocol::Message::OnQuestStart(event) => {
+                                    state.on_quest_start_event(event);
+                                }
+                                protocol::Message::OnEnemyCondition(event) => {
+                                    state.on_enemy_condition_event(event);
+                                }
+                                protocol::Message::OnEnemyDeath(event) => {
+                                    state.on_enemy_death_event(event);
+                                }
+                                protocol::Message::OnStatusChange(event) => {
+                                    state.on_status_change_event(event);
+                                }
+                                protocol::Message::OnSkillCast(event) => {
+                                    state.on_skill_cast_event(event);
+                                }
+                                // Only ever read back from saved logs.
+                                protocol::Message::Unknown { .. } => {}
                             }
                         }
                     }
@@ -653,6 +1000,13 @@ fn main() {
     // Setup the database.
     db::setup_db().expect("Failed to setup database");
 
+    // Logs saved before loadouts were recorded get them from their encounter, without holding up the launch.
+    std::thread::spawn(|| {
+        if let Err(e) = backfill_loadouts() {
+            warn!("Could not backfill loadouts: {:?}", e);
+        }
+    });
+
     info!("Database setup complete, launching application..");
 
     tauri::Builder::default()
@@ -670,6 +1024,7 @@ fn main() {
         .manage(AlwaysOnTop(AtomicBool::new(true)))
         .manage(ClickThrough(AtomicBool::new(false)))
         .manage(DebugMode(AtomicBool::new(false)))
+        .manage(AppLocalizer(RwLock::new(Localizer::default())))
         .system_tray(system_tray_with_menu())
         .on_system_tray_event(menu_tray_handler)
         .on_window_event(|event| {
@@ -680,14 +1035,37 @@ fn main() {
         })
         .invoke_handler(tauri::generate_handler![
             fetch_encounter_state,
+            fetch_rotation,
+            fetch_enemy_catalog,
+            fetch_loadout_history,
             fetch_logs,
             delete_logs,
             delete_all_logs,
             toggle_always_on_top,
             export_damage_log_to_file,
+            export_trait_totals_to_file,
             set_debug_mode,
+            set_locale,
         ])
         .setup(|app| {
+            // Load the child actor attribution rules shipped with the app, if they're valid.
+            if let Some(path) = app
+                .path_resolver()
+                .resolve_resource("assets/child-actor-rules.json")
+            {
+                match parser::attribution::ChildActorRules::from_file(&path) {
+                    Ok(rules) => {
+                        parser::attribution::install(rules);
+                    }
+                    Err(e) => warn!("Could not load child actor rules, using defaults: {:?}", e),
+                }
+            }
+
+            // Until the frontend sets the locale, exports use English names.
+            if let Some(lang_dir) = app.path_resolver().resolve_resource("lang") {
+                *app.state::<AppLocalizer>().0.write().unwrap() = Localizer::load(&lang_dir, "en");
+            }
+
             // Perform the game hook check in a separate thread.
             tauri::async_runtime::spawn(check_and_perform_hook(app.handle()));
 
@@ -0,0 +1,415 @@
+/*!
+Rules for attributing damage from child actors (pets, summons, transformations) to their parent character.
+
+The rules are keyed by the parent's `CharacterType` (e.g. `Pl0700`) and then by the child actor, which can be
+a `CharacterType` name (`Pl0700Ghost`), a raw actor hash (`0x5B1AB457`) or `*` to match every child of that parent.
+A child is matched by its name first, then by its hash, and only then by `*`.
+
+A default set of rules is embedded from `assets/child-actor-rules.json`, and the same file shipped with the
+app's resources is loaded at startup so that new pets can be handled without a rebuild.
+*/
+
+use std::{collections::HashMap, path::Path, sync::OnceLock};
+
+use anyhow::Result;
+use protocol::{ActionType, DamageEvent};
+use serde::Deserialize;
+
+use super::constants::CharacterType;
+
+const DEFAULT_RULES: &str = include_str!("../../assets/child-actor-rules.json");
+
+static RULES: OnceLock<ChildActorRules> = OnceLock::new();
+
+/// Returns the active attribution rules, falling back to the embedded defaults if none were installed.
+pub fn rules() -> &'static ChildActorRules {
+    RULES.get_or_init(ChildActorRules::embedded)
+}
+
+/// Installs the attribution rules to use for the rest of the session.
+/// Returns false if rules were already in use, in which case the given rules are discarded.
+pub fn install(rules: ChildActorRules) -> bool {
+    RULES.set(rules).is_ok()
+}
+
+/// Remaps the action ID of a child's hit to another action.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionRemap {
+    /// Actions that this remap applies to, matches every action if empty.
+    #[serde(default)]
+    pub from: Vec<ActionType>,
+    /// Actions that are left untouched by this remap.
+    #[serde(default)]
+    pub except: Vec<ActionType>,
+    /// Action to attribute the hit to.
+    pub to: ActionType,
+}
+
+impl ActionRemap {
+    fn matches(&self, action: &ActionType) -> bool {
+        (self.from.is_empty() || self.from.contains(action)) && !self.except.contains(action)
+    }
+}
+
+/// Attributes hits to the last known skill, for children whose action ID doesn't say which skill spawned them.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StickySkillRule {
+    /// Damage event flags that mark a hit as coming from a skill.
+    pub flag_mask: u64,
+    /// Skill IDs that become the last known skill when they are seen.
+    pub skills: Vec<u32>,
+    /// Action to use when no skill has been seen yet.
+    pub fallback: ActionType,
+}
+
+/// How damage from a single child actor should be attributed.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ChildActorRule {
+    /// Track this child's skills as the parent's own skills instead of a separate child.
+    #[serde(default)]
+    pub merge_into_parent: bool,
+    /// Action remaps applied in order, the first one matching wins.
+    #[serde(default)]
+    pub remap_actions: Vec<ActionRemap>,
+    /// Sticky last-skill attribution, checked before the remaps.
+    #[serde(default)]
+    pub sticky_skill: Option<StickySkillRule>,
+    /// Ignore player load events for this child, used for transformations that load as a separate player. With
+    /// `transformation` set, the load starts a transformation of the parent instead.
+    #[serde(default)]
+    pub ignore_player_load: bool,
+    /// This child is a transformation of the parent (e.g. Id's dragon form), hits from it count as transformed.
+    #[serde(default)]
+    pub transformation: bool,
+}
+
+impl ChildActorRule {
+    /// Resolves the action of a hit from this child, updating the given last known skill if needed.
+    pub fn resolve_action(
+        &self,
+        event: &DamageEvent,
+        last_known_skill: &mut Option<ActionType>,
+    ) -> ActionType {
+        if let Some(sticky) = &self.sticky_skill {
+            if event.flags & sticky.flag_mask != 0 {
+                if sticky
+                    .skills
+                    .iter()
+                    .any(|skill_id| ActionType::Normal(*skill_id) == event.action_id)
+                {
+                    *last_known_skill = Some(event.action_id);
+                }
+
+                return last_known_skill.unwrap_or(sticky.fallback);
+            }
+        }
+
+        self.remap_actions
+            .iter()
+            .find(|remap| remap.matches(&event.action_id))
+            .map(|remap| remap.to)
+            .unwrap_or(event.action_id)
+    }
+}
+
+/// Attribution rules for every parent character.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(transparent)]
+pub struct ChildActorRules {
+    parents: HashMap<String, HashMap<String, ChildActorRule>>,
+}
+
+impl ChildActorRules {
+    /// Parses and validates rules from a JSON string.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let rules: Self = serde_json::from_str(json)?;
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    /// Loads rules from a JSON file.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Rules that are compiled into the app.
+    pub fn embedded() -> Self {
+        Self::from_json(DEFAULT_RULES).expect("Embedded child actor rules should be valid")
+    }
+
+    fn validate(&self) -> Result<()> {
+        for (parent, children) in self.parents.iter() {
+            if CharacterType::from_name(parent).is_none() {
+                return Err(anyhow::anyhow!("Unknown parent character: {parent}"));
+            }
+
+            for (child, rule) in children.iter() {
+                if child != "*"
+                    && CharacterType::from_name(child).is_none()
+                    && parse_hash(child).is_none()
+                {
+                    return Err(anyhow::anyhow!(
+                        "Invalid child actor key for {parent}: {child}"
+                    ));
+                }
+
+                if let Some(hash) = parse_hash(child) {
+                    if children
+                        .keys()
+                        .any(|other| other != child && parse_hash(other) == Some(hash))
+                    {
+                        return Err(anyhow::anyhow!(
+                            "Duplicate child actor hash for {parent}: {child}"
+                        ));
+                    }
+                }
+
+                if let Some(sticky) = &rule.sticky_skill {
+                    if sticky.flag_mask == 0 {
+                        return Err(anyhow::anyhow!(
+                            "Sticky skill rule for {parent}/{child} needs a non-zero flag mask"
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Finds the rule for a child actor of the given parent, by its name first, then by its hash and then `*`.
+    pub fn child_rule(&self, parent: CharacterType, child_hash: u32) -> Option<&ChildActorRule> {
+        let children = self.parents.get(&parent.to_string())?;
+        let child_type = CharacterType::from_hash(child_hash);
+
+        let by_name = match child_type {
+            CharacterType::Unknown(_) => None,
+            _ => children.get(&child_type.to_string()),
+        };
+
+        // Hash keys are unique once validated, so at most one of them matches.
+        by_name
+            .or_else(|| {
+                children
+                    .iter()
+                    .find(|(key, _)| parse_hash(key) == Some(child_hash))
+                    .map(|(_, rule)| rule)
+            })
+            .or_else(|| children.get("*"))
+    }
+
+    /// Whether player load events for this character should be ignored.
+    pub fn ignores_player_load(&self, character_type: CharacterType) -> bool {
+        let character_type = character_type.to_string();
+
+        self.parents
+            .values()
+            .flat_map(|children| children.iter())
+            .any(|(key, rule)| rule.ignore_player_load && *key == character_type)
+    }
+
+    /// Whether the character has a transformation that is tracked separately.
+    pub fn has_transformation(&self, parent: CharacterType) -> bool {
+        self.parents
+            .get(&parent.to_string())
+            .is_some_and(|children| children.values().any(|rule| rule.transformation))
+    }
+
+    /// Character that the given character is a transformation of, for transformations that load as a separate player.
+    pub fn transformation_parent(&self, character_type: CharacterType) -> Option<CharacterType> {
+        let character_type = character_type.to_string();
+
+        self.parents
+            .iter()
+            .find(|(_, children)| {
+                children
+                    .iter()
+                    .any(|(key, rule)| rule.transformation && *key == character_type)
+            })
+            .and_then(|(parent, _)| CharacterType::from_name(parent))
+    }
+
+    /// Whether a child actor of the given parent is one of its transformations.
+    pub fn is_transformation(&self, parent: CharacterType, child_hash: u32) -> bool {
+        self.child_rule(parent, child_hash)
+            .is_some_and(|rule| rule.transformation)
+    }
+}
+
+fn parse_hash(key: &str) -> Option<u32> {
+    u32::from_str_radix(key.strip_prefix("0x")?, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use protocol::Actor;
+
+    use super::*;
+
+    fn pet_event(actor_type: u32, action_id: ActionType, flags: u64) -> DamageEvent {
+        DamageEvent {
+            source: Actor {
+                index: 1,
+                actor_type,
+                parent_actor_type: 0xFBA6615D,
+                parent_index: 0,
+            },
+            target: Actor {
+                index: 2,
+                actor_type: 0,
+                parent_actor_type: 0,
+                parent_index: 2,
+            },
+            action_id,
+            damage: 100,
+            flags,
+            attack_rate: None,
+            stun_value: None,
+            damage_cap: None,
+        }
+    }
+
+    #[test]
+    fn embedded_rules_are_valid() {
+        let rules = ChildActorRules::embedded();
+
+        assert!(rules
+            .child_rule(CharacterType::Pl0700, 0x2AF678E8)
+            .is_some());
+        assert!(rules
+            .child_rule(CharacterType::Pl0000, 0x2AF678E8)
+            .is_none());
+    }
+
+    #[test]
+    fn sticky_skill_attributes_to_last_known_skill() {
+        let rules = ChildActorRules::embedded();
+        let rule = rules.child_rule(CharacterType::Pl0700, 0x2AF678E8).unwrap();
+        let mut last_known_skill = None;
+
+        // Skill hits before any known skill fall back to the pet normal.
+        let action = rule.resolve_action(
+            &pet_event(0x2AF678E8, ActionType::Normal(10), 1 << 2),
+            &mut last_known_skill,
+        );
+        assert_eq!(action, ActionType::Normal(65));
+
+        let action = rule.resolve_action(
+            &pet_event(0x2AF678E8, ActionType::Normal(1500), 1 << 2),
+            &mut last_known_skill,
+        );
+        assert_eq!(action, ActionType::Normal(1500));
+
+        // Follow-up hits report the wrong action ID, but should stick to the last known skill.
+        let action = rule.resolve_action(
+            &pet_event(0x2AF678E8, ActionType::Normal(10), 1 << 2),
+            &mut last_known_skill,
+        );
+        assert_eq!(action, ActionType::Normal(1500));
+    }
+
+    #[test]
+    fn remaps_actions_except_excluded() {
+        let rules = ChildActorRules::embedded();
+        let rule = rules.child_rule(CharacterType::Pl0700, 0x2AF678E8).unwrap();
+        let mut last_known_skill = None;
+
+        let action = rule.resolve_action(
+            &pet_event(0x2AF678E8, ActionType::Normal(10), 0),
+            &mut last_known_skill,
+        );
+        assert_eq!(action, ActionType::Normal(65));
+
+        let action = rule.resolve_action(
+            &pet_event(0x2AF678E8, ActionType::LinkAttack, 0),
+            &mut last_known_skill,
+        );
+        assert_eq!(action, ActionType::LinkAttack);
+    }
+
+    #[test]
+    fn wildcard_and_hash_keys_match_children() {
+        let rules = ChildActorRules::from_json(
+            r#"{
+                "Pl2200": { "*": { "mergeIntoParent": true } },
+                "Pl1800": { "0xC9F45042": { "remapActions": [{ "to": "SBA" }] } }
+            }"#,
+        )
+        .unwrap();
+
+        assert!(
+            rules
+                .child_rule(CharacterType::Pl2200, 0x5B1AB457)
+                .unwrap()
+                .merge_into_parent
+        );
+        assert_eq!(
+            rules
+                .child_rule(CharacterType::Pl1800, 0xC9F45042)
+                .unwrap()
+                .remap_actions
+                .len(),
+            1
+        );
+        assert!(rules.child_rule(CharacterType::Pl1800, 0x1234).is_none());
+    }
+
+    #[test]
+    fn names_take_priority_over_hashes() {
+        let rules = ChildActorRules::from_json(
+            r#"{
+                "Pl0700": {
+                    "0x2AF678E8": { "mergeIntoParent": true },
+                    "Pl0700Ghost": { "remapActions": [{ "to": "SBA" }] }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let rule = rules.child_rule(CharacterType::Pl0700, 0x2AF678E8).unwrap();
+
+        assert!(!rule.merge_into_parent);
+        assert_eq!(rule.remap_actions.len(), 1);
+    }
+
+    #[test]
+    fn ignores_player_load_for_transformations() {
+        let rules = ChildActorRules::embedded();
+
+        assert!(rules.ignores_player_load(CharacterType::Pl2000));
+        assert!(!rules.ignores_player_load(CharacterType::Pl1900));
+    }
+
+    #[test]
+    fn transformations_are_tracked_for_their_parent() {
+        let rules = ChildActorRules::embedded();
+
+        assert!(rules.has_transformation(CharacterType::Pl1900));
+        assert!(!rules.has_transformation(CharacterType::Pl0700));
+        assert!(rules.is_transformation(CharacterType::Pl1900, 0xF5755C0E));
+        assert!(!rules.is_transformation(CharacterType::Pl1900, 0x8056ABCD));
+        assert_eq!(
+            rules.transformation_parent(CharacterType::Pl2000),
+            Some(CharacterType::Pl1900)
+        );
+        assert_eq!(rules.transformation_parent(CharacterType::Pl1900), None);
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        assert!(ChildActorRules::from_json(r#"{ "Pl0700": { "Ghost": {} } }"#).is_err());
+        assert!(ChildActorRules::from_json(r#"{ "Pl0700": { "Pl0700Gost": {} } }"#).is_err());
+        assert!(ChildActorRules::from_json(
+            r#"{ "Pl0700": { "0x2AF678E8": {}, "0x2af678e8": {} } }"#
+        )
+        .is_err());
+        assert!(ChildActorRules::from_json(r#"{ "Pl070": { "*": {} } }"#).is_err());
+        assert!(ChildActorRules::from_json(
+            r#"{ "Pl0700": { "*": { "stickySkill": { "flagMask": 0, "skills": [], "fallback": "SBA" } } } }"#
+        )
+        .is_err());
+    }
+}
@@ -1,6 +1,11 @@
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{value::StrDeserializer, IntoDeserializer},
+    Deserialize, Serialize,
+};
 use strum_macros::Display;
 
+use super::enemies::{self, EnemyCategory, EnemyInfo};
+
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Copy, Display)]
 pub enum CharacterType {
     /// Gran
@@ -91,6 +96,13 @@ impl CharacterType {
             _ => CharacterType::Unknown(hash),
         }
     }
+
+    /// Parses a known character from its name, e.g. `Pl0700`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let deserializer: StrDeserializer<serde::de::value::Error> = name.into_deserializer();
+
+        Self::deserialize(deserializer).ok()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Copy, Display)]
@@ -103,14 +115,20 @@ impl EnemyType {
     pub fn from_hash(hash: u32) -> Self {
         EnemyType::Unknown(hash)
     }
-}
 
-#[repr(u32)]
-#[derive(Copy, Clone)]
-pub enum FerrySkillId {
-    PetNormal = 65u32,
-    BlausGespenst = 1100u32,
-    Pendel = 1400u32,
-    Strafe = 1500u32,
-}
+    pub fn hash(&self) -> u32 {
+        match self {
+            EnemyType::Unknown(hash) => *hash,
+        }
+    }
+
+    /// Catalog entry for this enemy, if it is a known enemy.
+    pub fn info(&self) -> Option<&'static EnemyInfo> {
+        enemies::catalog().get(self.hash())
+    }
 
+    pub fn is_boss(&self) -> bool {
+        self.info()
+            .is_some_and(|info| info.category == EnemyCategory::Boss)
+    }
+}
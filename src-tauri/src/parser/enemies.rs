@@ -0,0 +1,218 @@
+/*!
+Catalog of known enemies, used to name enemies and tell bosses apart from adds and objects.
+
+Names come from the game's text in each language's `enemies.json`, keyed by the enemy's hash. The category comes
+from `assets/enemy-catalog.json`, keyed by the enemy's model key (e.g. `EM7700`), which lists every enemy with a name.
+Enemies missing from the catalog are adds if they are regular enemies (`EM*`), and objects otherwise (breakables,
+summoned weapons). The quests an enemy shows up in are not known up front, they come from the saved logs.
+*/
+
+use std::{collections::HashMap, sync::OnceLock};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+const CATALOG: &str = include_str!("../../assets/enemy-catalog.json");
+
+const NAMES: [(&str, &str); 10] = [
+    ("en", include_str!("../../lang/en/enemies.json")),
+    ("jp", include_str!("../../lang/jp/enemies.json")),
+    ("ko", include_str!("../../lang/ko/enemies.json")),
+    ("zh-CN", include_str!("../../lang/zh-CN/enemies.json")),
+    ("zh-TW", include_str!("../../lang/zh-TW/enemies.json")),
+    ("bp", include_str!("../../lang/bp/enemies.json")),
+    ("es", include_str!("../../lang/es/enemies.json")),
+    ("fr", include_str!("../../lang/fr/enemies.json")),
+    ("ge", include_str!("../../lang/ge/enemies.json")),
+    ("it", include_str!("../../lang/it/enemies.json")),
+];
+
+static CATALOG_INSTANCE: OnceLock<EnemyCatalog> = OnceLock::new();
+
+/// Returns the catalog of enemies that is compiled into the app.
+pub fn catalog() -> &'static EnemyCatalog {
+    CATALOG_INSTANCE.get_or_init(EnemyCatalog::embedded)
+}
+
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Copy)]
+pub enum EnemyCategory {
+    Boss,
+    Add,
+    /// Breakable parts, objectives and summoned weapons.
+    Object,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CatalogEntry {
+    category: EnemyCategory,
+}
+
+#[derive(Debug, Deserialize)]
+struct NameEntry {
+    key: String,
+    text: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EnemyInfo {
+    pub hash: u32,
+    /// Model key of the enemy, e.g. `EM7700`.
+    pub key: String,
+    pub category: EnemyCategory,
+    /// Name of the enemy by language.
+    names: HashMap<&'static str, String>,
+}
+
+impl EnemyInfo {
+    /// Name of the enemy in the given language, falling back to English.
+    pub fn name(&self, lang: &str) -> Option<&str> {
+        self.names
+            .get(lang)
+            .or_else(|| self.names.get("en"))
+            .map(String::as_str)
+    }
+}
+
+#[derive(Debug)]
+pub struct EnemyCatalog {
+    enemies: HashMap<u32, EnemyInfo>,
+}
+
+impl EnemyCatalog {
+    /// Builds the catalog from the category file and the enemy names of each language.
+    pub fn from_json(catalog: &str, names: &[(&'static str, &str)]) -> Result<Self> {
+        let entries: HashMap<String, CatalogEntry> = serde_json::from_str(catalog)?;
+        let mut enemies: HashMap<u32, EnemyInfo> = HashMap::new();
+
+        for (lang, json) in names {
+            let names: HashMap<String, NameEntry> = serde_json::from_str(json)?;
+
+            for (hash, name) in names {
+                let hash = u32::from_str_radix(&hash, 16)
+                    .map_err(|_| anyhow::anyhow!("Invalid enemy hash for {lang}: {hash}"))?;
+
+                let info = enemies.entry(hash).or_insert_with(|| EnemyInfo {
+                    hash,
+                    category: entries
+                        .get(&name.key)
+                        .map(|entry| entry.category)
+                        .unwrap_or_else(|| default_category(&name.key)),
+                    key: name.key.clone(),
+                    names: HashMap::new(),
+                });
+
+                info.names.insert(lang, name.text);
+            }
+        }
+
+        if let Some(key) = entries
+            .keys()
+            .find(|key| !enemies.values().any(|info| info.key == **key))
+        {
+            return Err(anyhow::anyhow!("Unknown enemy in catalog: {key}"));
+        }
+
+        Ok(Self { enemies })
+    }
+
+    /// Catalog that is compiled into the app.
+    pub fn embedded() -> Self {
+        Self::from_json(CATALOG, &NAMES).expect("Embedded enemy catalog should be valid")
+    }
+
+    pub fn get(&self, hash: u32) -> Option<&EnemyInfo> {
+        self.enemies.get(&hash)
+    }
+
+    /// Every known enemy, sorted by key.
+    pub fn enemies(&self) -> Vec<&EnemyInfo> {
+        let mut enemies: Vec<&EnemyInfo> = self.enemies.values().collect();
+        enemies.sort_by(|a, b| a.key.cmp(&b.key).then(a.hash.cmp(&b.hash)));
+        enemies
+    }
+}
+
+fn default_category(key: &str) -> EnemyCategory {
+    if key.starts_with("EM") {
+        EnemyCategory::Add
+    } else {
+        EnemyCategory::Object
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_catalog_is_valid() {
+        let catalog = EnemyCatalog::embedded();
+        let lucilius = catalog.get(0x2B31654B).unwrap();
+
+        assert_eq!(lucilius.key, "EM7700");
+        assert_eq!(lucilius.category, EnemyCategory::Boss);
+        assert_eq!(lucilius.name("en"), Some("Lucilius"));
+        assert_eq!(
+            catalog.get(0x427E32FE).unwrap().category,
+            EnemyCategory::Add
+        );
+    }
+
+    #[test]
+    fn embedded_catalog_lists_every_named_enemy() {
+        let entries: HashMap<String, CatalogEntry> = serde_json::from_str(CATALOG).unwrap();
+        let names: HashMap<String, NameEntry> = serde_json::from_str(NAMES[0].1).unwrap();
+
+        for name in names.values() {
+            assert!(
+                entries.contains_key(&name.key),
+                "{} is missing from the catalog",
+                name.key
+            );
+        }
+    }
+
+    #[test]
+    fn categories_default_by_key_and_names_fall_back_to_english() {
+        let catalog = EnemyCatalog::from_json(
+            r#"{ "EM0400": { "category": "Boss" } }"#,
+            &[
+                (
+                    "en",
+                    r#"{
+                        "00000001": { "key": "EM0400", "text": "Wyvern" },
+                        "00000002": { "key": "EM0000", "text": "Goblin Stormer" },
+                        "00000003": { "key": "BH0004", "text": "Barricade" }
+                    }"#,
+                ),
+                (
+                    "jp",
+                    r#"{ "00000001": { "key": "EM0400", "text": "ワイバーン" } }"#,
+                ),
+            ],
+        )
+        .unwrap();
+
+        let wyvern = catalog.get(1).unwrap();
+
+        assert_eq!(wyvern.category, EnemyCategory::Boss);
+        assert_eq!(wyvern.name("jp"), Some("ワイバーン"));
+        assert_eq!(catalog.get(2).unwrap().category, EnemyCategory::Add);
+        assert_eq!(catalog.get(2).unwrap().name("jp"), Some("Goblin Stormer"));
+        assert_eq!(catalog.get(3).unwrap().category, EnemyCategory::Object);
+    }
+
+    #[test]
+    fn rejects_unknown_catalog_keys() {
+        assert!(EnemyCatalog::from_json(
+            r#"{ "EM9999": { "category": "Boss" } }"#,
+            &[(
+                "en",
+                r#"{ "00000001": { "key": "EM0400", "text": "Wyvern" } }"#
+            )],
+        )
+        .is_err());
+    }
+}
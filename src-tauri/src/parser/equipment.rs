@@ -0,0 +1,387 @@
+/*!
+Catalogs of traits, sigils, weapons and overmasteries, used to decode the ids stored on a player's equipment.
+
+Names and keys come from the English text in `lang/en`, keyed by the id's hash, while the rest comes from
+`assets/equipment-catalog.json`:
+- `traits`: the level at which a trait stops having an effect, keyed by the trait's key (e.g. `SKILL_020_00`).
+  Only a handful of max levels are known so far (Enmity, Stamina, DMG Cap, Tyranny and Supplementary DMG), every
+  other trait is left out and its max level and overflow are reported as unknown.
+- `percentOvermasteries` and `flatOvermasteries`: overmasteries of stats that are normally raised by a flat amount
+  (Attack Power, Health and Stun Power), split by whether this one raises it by a percentage or a flat amount.
+  Ones that are in neither list are reported as unknown.
+*/
+
+use std::{collections::HashMap, sync::OnceLock};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::constants::CharacterType;
+
+/// Id used by the game for empty trait, sigil and wrightstone slots.
+pub const EMPTY_ID: u32 = 0x887AE0B0;
+
+const CATALOG: &str = include_str!("../../assets/equipment-catalog.json");
+
+const TRAITS: &str = include_str!("../../lang/en/traits.json");
+const SIGILS: &str = include_str!("../../lang/en/sigils.json");
+const WEAPONS: &str = include_str!("../../lang/en/weapons.json");
+const OVERMASTERIES: &str = include_str!("../../lang/en/overmasteries.json");
+
+static CATALOG_INSTANCE: OnceLock<EquipmentCatalog> = OnceLock::new();
+
+/// Returns the catalog of equipment that is compiled into the app.
+pub fn catalog() -> &'static EquipmentCatalog {
+    CATALOG_INSTANCE.get_or_init(EquipmentCatalog::embedded)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CatalogFile {
+    #[serde(default)]
+    traits: HashMap<String, TraitEntry>,
+    #[serde(default)]
+    percent_overmasteries: Vec<String>,
+    #[serde(default)]
+    flat_overmasteries: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TraitEntry {
+    max_level: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NameEntry {
+    key: String,
+    text: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TraitInfo {
+    pub hash: u32,
+    /// Key of the trait, e.g. `SKILL_020_00`.
+    pub key: String,
+    pub name: String,
+    /// Level past which the trait has no further effect, if known.
+    pub max_level: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SigilInfo {
+    pub hash: u32,
+    /// Key of the sigil, e.g. `GEEN_020_13`.
+    pub key: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WeaponTypeInfo {
+    pub hash: u32,
+    /// Key of the weapon, e.g. `WEP_PL0100_06_01`.
+    pub key: String,
+    pub name: String,
+    /// Character that wields the weapon, taken from its key.
+    pub character_type: Option<CharacterType>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Copy)]
+pub enum OvermasteryStat {
+    AttackPower,
+    Health,
+    CriticalHitRate,
+    StunPower,
+    NormalAttackDamageCap,
+    SkillDamageCap,
+    SkyboundArtDamageCap,
+    SkillDamage,
+    SkyboundArtDamage,
+    ChainBurstDamage,
+    HealingCap,
+    Unknown,
+}
+
+impl OvermasteryStat {
+    fn from_name(name: &str) -> Self {
+        match name {
+            "Attack Power Up" => Self::AttackPower,
+            "Health Up" => Self::Health,
+            "Critical Hit Rate Up" => Self::CriticalHitRate,
+            "Stun Power Up" => Self::StunPower,
+            "Normal Attack Damage Cap Up" => Self::NormalAttackDamageCap,
+            "Skill Damage Cap Up" => Self::SkillDamageCap,
+            "Skybound Art Damage Cap Up" => Self::SkyboundArtDamageCap,
+            "Skill Damage Up" => Self::SkillDamage,
+            "Skybound Art Damage Up" => Self::SkyboundArtDamage,
+            "Chain Burst Damage Up" => Self::ChainBurstDamage,
+            "Healing Cap Up" => Self::HealingCap,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Whether the stat is normally raised by a flat amount rather than a percentage.
+    fn is_flat(self) -> bool {
+        matches!(self, Self::AttackPower | Self::Health | Self::StunPower)
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OvermasteryTypeInfo {
+    pub hash: u32,
+    pub key: String,
+    pub name: String,
+    pub stat: OvermasteryStat,
+    /// Whether the value is a percentage (`+5%`) instead of a flat amount (`+500`), `None` if it isn't known.
+    pub is_percent: Option<bool>,
+}
+
+/// The stat an overmastery raises, and by how much.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OvermasteryBonus {
+    pub stat: OvermasteryStat,
+    pub value: f32,
+    pub is_percent: Option<bool>,
+}
+
+impl OvermasteryTypeInfo {
+    pub fn bonus(&self, value: f32) -> OvermasteryBonus {
+        OvermasteryBonus {
+            stat: self.stat,
+            value,
+            is_percent: self.is_percent,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct EquipmentCatalog {
+    traits: HashMap<u32, TraitInfo>,
+    sigils: HashMap<u32, SigilInfo>,
+    weapons: HashMap<u32, WeaponTypeInfo>,
+    overmasteries: HashMap<u32, OvermasteryTypeInfo>,
+}
+
+impl EquipmentCatalog {
+    /// Builds the catalog from the catalog file and the English names of each kind of equipment.
+    pub fn from_json(
+        catalog: &str,
+        traits: &str,
+        sigils: &str,
+        weapons: &str,
+        overmasteries: &str,
+    ) -> Result<Self> {
+        let catalog: CatalogFile = serde_json::from_str(catalog)?;
+
+        let traits = parse_names(traits, "trait", |hash, name| TraitInfo {
+            hash,
+            max_level: catalog
+                .traits
+                .get(&name.key)
+                .and_then(|entry| entry.max_level),
+            key: name.key,
+            name: name.text,
+        })?;
+
+        let sigils = parse_names(sigils, "sigil", |hash, name| SigilInfo {
+            hash,
+            key: name.key,
+            name: name.text,
+        })?;
+
+        let weapons = parse_names(weapons, "weapon", |hash, name| WeaponTypeInfo {
+            hash,
+            character_type: weapon_character_type(&name.key),
+            key: name.key,
+            name: name.text,
+        })?;
+
+        let percent_overmasteries = parse_hashes(&catalog.percent_overmasteries)?;
+        let flat_overmasteries = parse_hashes(&catalog.flat_overmasteries)?;
+
+        let overmasteries = parse_names(overmasteries, "overmastery", |hash, name| {
+            let stat = OvermasteryStat::from_name(&name.text);
+
+            OvermasteryTypeInfo {
+                hash,
+                key: name.key,
+                name: name.text,
+                stat,
+                is_percent: if !stat.is_flat() || percent_overmasteries.contains(&hash) {
+                    Some(true)
+                } else if flat_overmasteries.contains(&hash) {
+                    Some(false)
+                } else {
+                    None
+                },
+            }
+        })?;
+
+        if let Some(key) = catalog
+            .traits
+            .keys()
+            .find(|key| !traits.values().any(|info| info.key == **key))
+        {
+            return Err(anyhow::anyhow!("Unknown trait in catalog: {key}"));
+        }
+
+        if let Some(hash) = percent_overmasteries
+            .iter()
+            .chain(flat_overmasteries.iter())
+            .find(|hash| !overmasteries.contains_key(hash))
+        {
+            return Err(anyhow::anyhow!(
+                "Unknown overmastery in catalog: {hash:08x}"
+            ));
+        }
+
+        Ok(Self {
+            traits,
+            sigils,
+            weapons,
+            overmasteries,
+        })
+    }
+
+    /// Catalog that is compiled into the app.
+    pub fn embedded() -> Self {
+        Self::from_json(CATALOG, TRAITS, SIGILS, WEAPONS, OVERMASTERIES)
+            .expect("Embedded equipment catalog should be valid")
+    }
+
+    pub fn trait_info(&self, hash: u32) -> Option<&TraitInfo> {
+        self.traits.get(&hash)
+    }
+
+    pub fn sigil(&self, hash: u32) -> Option<&SigilInfo> {
+        self.sigils.get(&hash)
+    }
+
+    pub fn weapon(&self, hash: u32) -> Option<&WeaponTypeInfo> {
+        self.weapons.get(&hash)
+    }
+
+    pub fn overmastery(&self, hash: u32) -> Option<&OvermasteryTypeInfo> {
+        self.overmasteries.get(&hash)
+    }
+}
+
+/// Parses a list of hex hashes from the catalog file.
+fn parse_hashes(keys: &[String]) -> Result<Vec<u32>> {
+    keys.iter()
+        .map(|key| {
+            u32::from_str_radix(key, 16)
+                .map_err(|_| anyhow::anyhow!("Invalid overmastery hash in catalog: {key}"))
+        })
+        .collect()
+}
+
+/// Parses a `lang` file of `hash -> { key, text }` entries.
+fn parse_names<T>(
+    json: &str,
+    kind: &str,
+    mut info: impl FnMut(u32, NameEntry) -> T,
+) -> Result<HashMap<u32, T>> {
+    let names: HashMap<String, NameEntry> = serde_json::from_str(json)?;
+
+    names
+        .into_iter()
+        .map(|(hash, name)| {
+            let hash = u32::from_str_radix(&hash, 16)
+                .map_err(|_| anyhow::anyhow!("Invalid {kind} hash: {hash}"))?;
+
+            Ok((hash, info(hash, name)))
+        })
+        .collect()
+}
+
+/// Character that wields a weapon, e.g. `WEP_PL0100_06_01` is one of Djeeta's (`Pl0100`) weapons.
+fn weapon_character_type(key: &str) -> Option<CharacterType> {
+    let model = key.strip_prefix("WEP_PL")?.get(..4)?;
+
+    CharacterType::from_name(&format!("Pl{model}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_catalog_is_valid() {
+        let catalog = EquipmentCatalog::embedded();
+
+        let damage_cap = catalog.trait_info(0xDC584F60).unwrap();
+        assert_eq!(damage_cap.key, "SKILL_020_00");
+        assert_eq!(damage_cap.name, "DMG Cap");
+        assert_eq!(damage_cap.max_level, Some(15));
+
+        let weapon = catalog
+            .weapons
+            .values()
+            .find(|weapon| weapon.key == "WEP_PL0100_06_01")
+            .unwrap();
+        assert_eq!(weapon.character_type, Some(CharacterType::Pl0100));
+
+        let health = catalog.overmastery(0x032A5217).unwrap();
+        assert_eq!(health.stat, OvermasteryStat::Health);
+
+        let attack = catalog.overmastery(0xD4B918EA).unwrap();
+        assert_eq!(attack.stat, OvermasteryStat::AttackPower);
+        assert_eq!(attack.is_percent, Some(true));
+    }
+
+    #[test]
+    fn overmasteries_are_flat_or_percent_by_stat() {
+        let catalog = EquipmentCatalog::from_json(
+            r#"{ "percentOvermasteries": ["00000003"], "flatOvermasteries": ["00000001"] }"#,
+            "{}",
+            "{}",
+            "{}",
+            r#"{
+                "00000001": { "key": "00000001", "text": "Health Up" },
+                "00000002": { "key": "00000002", "text": "Critical Hit Rate Up" },
+                "00000003": { "key": "00000003", "text": "Attack Power Up" },
+                "00000004": { "key": "00000004", "text": "Stun Power Up" }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            catalog.overmastery(1).unwrap().bonus(500.0),
+            OvermasteryBonus {
+                stat: OvermasteryStat::Health,
+                value: 500.0,
+                is_percent: Some(false),
+            }
+        );
+        assert_eq!(catalog.overmastery(2).unwrap().is_percent, Some(true));
+        assert_eq!(catalog.overmastery(3).unwrap().is_percent, Some(true));
+        // Flat stats that aren't in either list could be either.
+        assert_eq!(catalog.overmastery(4).unwrap().is_percent, None);
+    }
+
+    #[test]
+    fn rejects_unknown_catalog_keys() {
+        assert!(EquipmentCatalog::from_json(
+            r#"{ "traits": { "SKILL_999_00": { "maxLevel": 15 } } }"#,
+            r#"{ "dc584f60": { "key": "SKILL_020_00", "text": "DMG Cap" } }"#,
+            "{}",
+            "{}",
+            "{}",
+        )
+        .is_err());
+        assert!(EquipmentCatalog::from_json(
+            r#"{ "percentOvermasteries": ["00000001"] }"#,
+            "{}",
+            "{}",
+            "{}",
+            "{}",
+        )
+        .is_err());
+    }
+}
@@ -1,4 +1,7 @@
+pub mod attribution;
 pub mod constants;
+pub mod enemies;
+pub mod equipment;
 pub mod v0;
 
 #[allow(dead_code)]
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{PlayerData, Sigil, WeaponInfo};
+use crate::parser::equipment::{self, EMPTY_ID};
+
+/// Total level of a trait across a player's sigils and weapon.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TraitTotal {
+    pub trait_id: u32,
+    pub level: u32,
+    /// Level past which the trait has no further effect, if known.
+    pub max_level: Option<u32>,
+    /// Levels past the max level, which are wasted. `None` if the max level isn't known.
+    pub overflow: Option<u32>,
+}
+
+/// Sums the levels of each trait on the sigils and weapon, including the weapon's wrightstone traits.
+/// Traits are sorted by their total level, highest first.
+pub(super) fn trait_totals(sigils: &[Sigil], weapon_info: Option<&WeaponInfo>) -> Vec<TraitTotal> {
+    let mut levels: HashMap<u32, u32> = HashMap::new();
+
+    let traits = sigils
+        .iter()
+        .flat_map(Sigil::traits)
+        .chain(weapon_info.map(WeaponInfo::traits).unwrap_or_default());
+
+    for (trait_id, level) in traits {
+        *levels.entry(trait_id).or_default() += level;
+    }
+
+    let mut totals: Vec<TraitTotal> = levels
+        .into_iter()
+        .map(|(trait_id, level)| {
+            let max_level = equipment::catalog()
+                .trait_info(trait_id)
+                .and_then(|info| info.max_level);
+
+            TraitTotal {
+                trait_id,
+                level,
+                max_level,
+                overflow: max_level.map(|max_level| level.saturating_sub(max_level)),
+            }
+        })
+        .collect();
+
+    totals.sort_by(|a, b| b.level.cmp(&a.level).then(a.trait_id.cmp(&b.trait_id)));
+    totals
+}
+
+/// Fingerprint of the weapon, sigils and overmasteries a player has equipped, as a hex string. Sigils and
+/// overmasteries are sorted first so that the fingerprint doesn't depend on the order they're equipped in.
+pub(super) fn loadout_fingerprint(player: &PlayerData) -> String {
+    let mut values: Vec<u32> = Vec::new();
+
+    if let Some(weapon) = &player.weapon_info {
+        values.extend([
+            weapon.weapon_id,
+            weapon.star_level,
+            weapon.plus_marks,
+            weapon.awakening_level,
+            weapon.wrightstone_id,
+        ]);
+        values.extend(
+            weapon
+                .traits()
+                .into_iter()
+                .flat_map(|(id, level)| [id, level]),
+        );
+    }
+
+    let mut sigils: Vec<[u32; 6]> = player
+        .sigils
+        .iter()
+        .filter(|sigil| sigil.sigil_id != EMPTY_ID)
+        .map(|sigil| {
+            [
+                sigil.sigil_id,
+                sigil.sigil_level,
+                sigil.first_trait_id,
+                sigil.first_trait_level,
+                sigil.second_trait_id,
+                sigil.second_trait_level,
+            ]
+        })
+        .collect();
+    sigils.sort_unstable();
+    values.extend(sigils.into_iter().flatten());
+
+    if let Some(overmastery_info) = &player.overmastery_info {
+        let mut overmasteries: Vec<[u32; 2]> = overmastery_info
+            .overmasteries
+            .iter()
+            .map(|overmastery| [overmastery.id, overmastery.value.to_bits()])
+            .collect();
+        overmasteries.sort_unstable();
+        values.extend(overmasteries.into_iter().flatten());
+    }
+
+    // FNV-1a, so fingerprints stay the same across versions of the app.
+    let hash = values
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .fold(0xCBF29CE484222325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001B3)
+        });
+
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::constants::CharacterType;
+
+    const DAMAGE_CAP: u32 = 0xDC584F60;
+    const ATK: u32 = 0x50079A1C;
+
+    fn sigil(first: (u32, u32), second: (u32, u32)) -> Sigil {
+        Sigil {
+            first_trait_id: first.0,
+            first_trait_level: first.1,
+            second_trait_id: second.0,
+            second_trait_level: second.1,
+            sigil_id: 0,
+            equipped_character: 0,
+            sigil_level: 15,
+            acquisition_count: 0,
+            notification_enum: 1,
+        }
+    }
+
+    #[test]
+    fn sums_sigil_and_weapon_traits() {
+        let sigils = vec![
+            sigil((DAMAGE_CAP, 10), (ATK, 5)),
+            sigil((ATK, 15), (EMPTY_ID, 0)),
+        ];
+        let weapon_info = WeaponInfo {
+            weapon_id: 0,
+            star_level: 6,
+            plus_marks: 99,
+            awakening_level: 10,
+            trait_1_id: DAMAGE_CAP,
+            trait_1_level: 7,
+            trait_2_id: EMPTY_ID,
+            trait_2_level: 0,
+            trait_3_id: EMPTY_ID,
+            trait_3_level: 0,
+            wrightstone_id: EMPTY_ID,
+            weapon_level: 150,
+            weapon_hp: 0,
+            weapon_attack: 0,
+        };
+
+        let totals = trait_totals(&sigils, Some(&weapon_info));
+
+        assert_eq!(
+            totals,
+            vec![
+                TraitTotal {
+                    trait_id: ATK,
+                    level: 20,
+                    max_level: None,
+                    overflow: None,
+                },
+                TraitTotal {
+                    trait_id: DAMAGE_CAP,
+                    level: 17,
+                    max_level: Some(15),
+                    overflow: Some(2),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn fingerprint_ignores_sigil_order_but_not_levels() {
+        let player = |sigils: Vec<Sigil>| PlayerData {
+            actor_index: 0,
+            display_name: "Test".to_string(),
+            character_name: "Test".to_string(),
+            character_type: CharacterType::Pl0000,
+            sigils,
+            is_online: false,
+            party_index: 0,
+            weapon_info: None,
+            overmastery_info: None,
+            player_stats: None,
+            trait_totals: Vec::new(),
+        };
+
+        let a = sigil((DAMAGE_CAP, 15), (EMPTY_ID, 0));
+        let b = sigil((ATK, 15), (EMPTY_ID, 0));
+        let fingerprint = loadout_fingerprint(&player(vec![a.clone(), b.clone()]));
+
+        assert_eq!(fingerprint, loadout_fingerprint(&player(vec![b, a])));
+        assert_ne!(
+            fingerprint,
+            loadout_fingerprint(&player(vec![sigil((DAMAGE_CAP, 11), (EMPTY_ID, 0))]))
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use protocol::EnemyCondition;
+use serde::{Deserialize, Serialize};
+
+/// A single period of time an enemy spent in a condition.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConditionWindow {
+    pub start_time: i64,
+    /// None while the condition is still active.
+    pub end_time: Option<i64>,
+    /// Index of the player whose hit started the condition.
+    pub trigger_index: Option<u32>,
+    /// Damage dealt to the enemy while the condition was active.
+    pub damage: u64,
+}
+
+/// Derived breakdown of a condition (e.g. stun) for an enemy.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConditionState {
+    pub condition: EnemyCondition,
+    /// Number of times the enemy entered this condition.
+    pub count: u32,
+    /// Time spent in the condition in milliseconds, only counting windows that have ended.
+    pub total_time: i64,
+    /// Damage dealt to the enemy while it was in this condition.
+    pub total_damage: u64,
+    pub windows: Vec<ConditionWindow>,
+}
+
+impl ConditionState {
+    pub fn new(condition: EnemyCondition) -> Self {
+        Self {
+            condition,
+            count: 0,
+            total_time: 0,
+            total_damage: 0,
+            windows: Vec::new(),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.windows
+            .last()
+            .is_some_and(|window| window.end_time.is_none())
+    }
+
+    /// Starts a new window, ending the previous one if it was still active.
+    pub fn start(&mut self, now: i64, trigger_index: Option<u32>) {
+        self.end(now);
+
+        self.count += 1;
+        self.windows.push(ConditionWindow {
+            start_time: now,
+            end_time: None,
+            trigger_index,
+            damage: 0,
+        });
+    }
+
+    /// Ends the active window, if any.
+    pub fn end(&mut self, now: i64) {
+        if let Some(window) = self.windows.last_mut() {
+            if window.end_time.is_none() {
+                window.end_time = Some(now);
+                self.total_time += now - window.start_time;
+            }
+        }
+    }
+
+    pub fn add_damage(&mut self, damage: u64) {
+        if let Some(window) = self.windows.last_mut() {
+            if window.end_time.is_none() {
+                window.damage += damage;
+                self.total_damage += damage;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_windows_and_damage_during_them() {
+        let mut stun = ConditionState::new(EnemyCondition::Stun);
+
+        stun.add_damage(100);
+        stun.start(1000, Some(0));
+        stun.add_damage(500);
+        assert!(stun.is_active());
+
+        stun.end(3000);
+        stun.add_damage(100);
+        stun.end(4000);
+
+        // Starting while active closes the previous window first.
+        stun.start(5000, Some(1));
+        stun.start(6000, None);
+
+        assert!(stun.is_active());
+        assert_eq!(stun.count, 3);
+        assert_eq!(stun.total_time, 3000);
+        assert_eq!(stun.total_damage, 500);
+        assert_eq!(
+            stun.windows[0],
+            ConditionWindow {
+                start_time: 1000,
+                end_time: Some(3000),
+                trigger_index: Some(0),
+                damage: 500,
+            }
+        );
+        assert_eq!(stun.windows[1].end_time, Some(6000));
+    }
+}
@@ -0,0 +1,444 @@
+//! (De)serialization of the raw event log that tolerates messages from newer versions.
+//!
+//! Messages that can't be decoded are kept as `Message::Unknown` with their encoded payload, and
+//! written back out in their original form so that a newer version can still read them. Unit variants are
+//! stored as just their tag, so they're kept with an empty payload.
+
+use std::fmt;
+
+use protocol::Message;
+use serde::{
+    de::{
+        self, value::StrDeserializer, DeserializeSeed, EnumAccess, IgnoredAny, IntoDeserializer,
+        MapAccess, SeqAccess, VariantAccess, Visitor,
+    },
+    ser::{self, SerializeMap, SerializeSeq},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// A decoded CBOR value, keeping enough of the original types to encode it back the same way.
+#[derive(Debug, Clone, PartialEq)]
+enum RawValue {
+    Null,
+    Bool(bool),
+    Unsigned(u64),
+    Signed(i64),
+    Unsigned128(u128),
+    Signed128(i128),
+    Float32(f32),
+    Float64(f64),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<RawValue>),
+    Map(Vec<(RawValue, RawValue)>),
+}
+
+impl Serialize for RawValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            RawValue::Null => serializer.serialize_none(),
+            RawValue::Bool(value) => serializer.serialize_bool(*value),
+            RawValue::Unsigned(value) => serializer.serialize_u64(*value),
+            RawValue::Signed(value) => serializer.serialize_i64(*value),
+            RawValue::Unsigned128(value) => serializer.serialize_u128(*value),
+            RawValue::Signed128(value) => serializer.serialize_i128(*value),
+            RawValue::Float32(value) => serializer.serialize_f32(*value),
+            RawValue::Float64(value) => serializer.serialize_f64(*value),
+            RawValue::Bytes(value) => serializer.serialize_bytes(value),
+            RawValue::Text(value) => serializer.serialize_str(value),
+            RawValue::Array(values) => {
+                let mut seq = serializer.serialize_seq(Some(values.len()))?;
+                for value in values {
+                    seq.serialize_element(value)?;
+                }
+                seq.end()
+            }
+            RawValue::Map(entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+struct RawValueVisitor;
+
+impl<'de> Visitor<'de> for RawValueVisitor {
+    type Value = RawValue;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any CBOR value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<RawValue, E> {
+        Ok(RawValue::Bool(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<RawValue, E> {
+        Ok(RawValue::Signed(value))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<RawValue, E> {
+        Ok(RawValue::Unsigned(value))
+    }
+
+    fn visit_i128<E>(self, value: i128) -> Result<RawValue, E> {
+        Ok(RawValue::Signed128(value))
+    }
+
+    fn visit_u128<E>(self, value: u128) -> Result<RawValue, E> {
+        Ok(RawValue::Unsigned128(value))
+    }
+
+    fn visit_f32<E>(self, value: f32) -> Result<RawValue, E> {
+        Ok(RawValue::Float32(value))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<RawValue, E> {
+        Ok(RawValue::Float64(value))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<RawValue, E> {
+        Ok(RawValue::Text(value.to_string()))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<RawValue, E> {
+        Ok(RawValue::Text(value))
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<RawValue, E> {
+        Ok(RawValue::Bytes(value.to_vec()))
+    }
+
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<RawValue, E> {
+        Ok(RawValue::Bytes(value))
+    }
+
+    fn visit_none<E>(self) -> Result<RawValue, E> {
+        Ok(RawValue::Null)
+    }
+
+    fn visit_unit<E>(self) -> Result<RawValue, E> {
+        Ok(RawValue::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<RawValue, D::Error> {
+        RawValue::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<RawValue, A::Error> {
+        let mut values = Vec::new();
+
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+
+        Ok(RawValue::Array(values))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<RawValue, A::Error> {
+        let mut entries = Vec::new();
+
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+
+        Ok(RawValue::Map(entries))
+    }
+}
+
+impl<'de> Deserialize<'de> for RawValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(RawValueVisitor)
+    }
+}
+
+/// Hands a message's payload to the typed decoder once its variant tag has been read, so that the payload can
+/// still be read as a `RawValue` if the tag turns out to be unknown.
+struct TaggedPayload<'a, A> {
+    tag: &'a str,
+    map: &'a mut A,
+    /// Set once the tag is recognised, from then on the payload is consumed by the typed decoder.
+    known: &'a mut bool,
+}
+
+impl<'de, A: MapAccess<'de>> Deserializer<'de> for TaggedPayload<'_, A> {
+    type Error = A::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, A::Error> {
+        visitor.visit_enum(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option unit
+        unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de, A: MapAccess<'de>> EnumAccess<'de> for TaggedPayload<'_, A> {
+    type Error = A::Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), A::Error> {
+        // `Unknown` only exists in memory, logs store the original tag instead.
+        if self.tag == "Unknown" {
+            return Err(de::Error::unknown_variant(self.tag, &[]));
+        }
+
+        let tag: StrDeserializer<A::Error> = self.tag.into_deserializer();
+        let variant = seed.deserialize(tag)?;
+        *self.known = true;
+
+        Ok((variant, self))
+    }
+}
+
+impl<'de, A: MapAccess<'de>> VariantAccess<'de> for TaggedPayload<'_, A> {
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), A::Error> {
+        self.map.next_value()
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, A::Error> {
+        self.map.next_value_seed(seed)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        _visitor: V,
+    ) -> Result<V::Value, A::Error> {
+        Err(de::Error::invalid_type(
+            de::Unexpected::TupleVariant,
+            &"a newtype variant",
+        ))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, A::Error> {
+        Err(de::Error::invalid_type(
+            de::Unexpected::StructVariant,
+            &"a newtype variant",
+        ))
+    }
+}
+
+/// Decodes a message, falling back to `Message::Unknown` if this version doesn't know its tag.
+struct LogMessageVisitor;
+
+impl<'de> Visitor<'de> for LogMessageVisitor {
+    type Value = Message;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a message")
+    }
+
+    // Unit variants are stored as just the variant name.
+    fn visit_str<E: de::Error>(self, tag: &str) -> Result<Message, E> {
+        self.visit_string(tag.to_string())
+    }
+
+    fn visit_string<E: de::Error>(self, tag: String) -> Result<Message, E> {
+        let deserializer: StrDeserializer<E> = tag.as_str().into_deserializer();
+
+        match Message::deserialize(deserializer) {
+            Ok(message) => Ok(message),
+            Err(_) => Ok(Message::Unknown {
+                tag,
+                raw: Vec::new(),
+            }),
+        }
+    }
+
+    // Messages are stored as a map of the variant name to its payload.
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Message, A::Error> {
+        let tag: String = map
+            .next_key()?
+            .ok_or_else(|| de::Error::custom("expected a message"))?;
+        let mut known = false;
+
+        let payload = TaggedPayload {
+            tag: &tag,
+            map: &mut map,
+            known: &mut known,
+        };
+
+        let message = match Message::deserialize(payload) {
+            Ok(message) => message,
+            Err(error) if known => return Err(error),
+            Err(_) => {
+                let payload: RawValue = map.next_value()?;
+                let raw =
+                    cbor4ii::serde::to_vec(Vec::new(), &payload).map_err(de::Error::custom)?;
+
+                Message::Unknown { tag, raw }
+            }
+        };
+
+        if map.next_key::<IgnoredAny>()?.is_some() {
+            return Err(de::Error::custom("expected a message"));
+        }
+
+        Ok(message)
+    }
+}
+
+struct LogMessage(Message);
+
+impl<'de> Deserialize<'de> for LogMessage {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_any(LogMessageVisitor)
+            .map(LogMessage)
+    }
+}
+
+struct LogMessageRef<'a>(&'a Message);
+
+impl Serialize for LogMessageRef<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Message::Unknown { tag, raw } if raw.is_empty() => serializer.serialize_str(tag),
+            Message::Unknown { tag, raw } => {
+                let payload: RawValue =
+                    cbor4ii::serde::from_slice(raw).map_err(ser::Error::custom)?;
+
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(tag, &payload)?;
+                map.end()
+            }
+            message => message.serialize(serializer),
+        }
+    }
+}
+
+pub fn serialize<S: Serializer>(log: &[(i64, Message)], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(
+        log.iter()
+            .map(|(timestamp, message)| (timestamp, LogMessageRef(message))),
+    )
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<(i64, Message)>, D::Error> {
+    let log: Vec<(i64, LogMessage)> = Vec::deserialize(deserializer)?;
+
+    Ok(log
+        .into_iter()
+        .map(|(timestamp, LogMessage(message))| (timestamp, message))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use protocol::{AreaEnterEvent, MessagesDroppedEvent};
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct Log {
+        #[serde(with = "super")]
+        events: Vec<(i64, Message)>,
+    }
+
+    /// Stands in for a `Message` with a variant added by a newer version.
+    #[derive(Serialize)]
+    enum NewerMessage {
+        OnAreaEnter(AreaEnterEvent),
+        OnSomethingNew { value: f32, name: String },
+        SomethingEmpty,
+    }
+
+    #[derive(Serialize)]
+    struct NewerLog {
+        events: Vec<(i64, NewerMessage)>,
+    }
+
+    #[test]
+    fn unknown_messages_survive_a_round_trip() {
+        let newer = cbor4ii::serde::to_vec(
+            Vec::new(),
+            &NewerLog {
+                events: vec![
+                    (
+                        1,
+                        NewerMessage::OnAreaEnter(AreaEnterEvent {
+                            last_known_quest_id: 1,
+                            last_known_elapsed_time_in_secs: 2,
+                        }),
+                    ),
+                    (
+                        2,
+                        NewerMessage::OnSomethingNew {
+                            value: 1.5,
+                            name: "new".to_string(),
+                        },
+                    ),
+                ],
+            },
+        )
+        .unwrap();
+
+        let log: Log = cbor4ii::serde::from_slice(&newer).unwrap();
+
+        assert!(matches!(log.events[0].1, Message::OnAreaEnter(_)));
+        assert!(
+            matches!(&log.events[1].1, Message::Unknown { tag, .. } if tag == "OnSomethingNew")
+        );
+
+        // Saving it again writes the message back as the newer version stored it.
+        let saved = cbor4ii::serde::to_vec(Vec::new(), &log).unwrap();
+        assert_eq!(saved, newer);
+    }
+
+    #[test]
+    fn unknown_unit_messages_survive_a_round_trip() {
+        let newer = cbor4ii::serde::to_vec(
+            Vec::new(),
+            &NewerLog {
+                events: vec![(1, NewerMessage::SomethingEmpty)],
+            },
+        )
+        .unwrap();
+
+        let log: Log = cbor4ii::serde::from_slice(&newer).unwrap();
+
+        assert!(matches!(
+            &log.events[0].1,
+            Message::Unknown { tag, raw } if tag == "SomethingEmpty" && raw.is_empty()
+        ));
+
+        let saved = cbor4ii::serde::to_vec(Vec::new(), &log).unwrap();
+        assert_eq!(saved, newer);
+    }
+
+    #[test]
+    fn known_messages_are_decoded() {
+        let log = Log {
+            events: vec![(
+                1,
+                Message::MessagesDropped(MessagesDroppedEvent {
+                    dropped: 3,
+                    total_dropped: 3,
+                }),
+            )],
+        };
+
+        let bytes = cbor4ii::serde::to_vec(Vec::new(), &log).unwrap();
+        let log: Log = cbor4ii::serde::from_slice(&bytes).unwrap();
+
+        assert!(matches!(
+            log.events[0].1,
+            Message::MessagesDropped(MessagesDroppedEvent { dropped: 3, .. })
+        ));
+    }
+}
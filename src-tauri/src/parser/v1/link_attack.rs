@@ -0,0 +1,273 @@
+use protocol::{ActionType, Message};
+use serde::Serialize;
+
+use crate::parser::constants::EnemyType;
+
+use super::{is_targeted, Parser};
+
+/// Link attack hits within this long of the previous one are counted as part of the same link, in milliseconds.
+const LINK_WINDOW: i64 = 3_000;
+
+/// Damage done by a player during a single link attack.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkParticipant {
+    pub index: u32,
+    pub hits: u32,
+    pub damage: u64,
+}
+
+/// A single link attack, made up of the link attack hits of everyone that joined in.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkAttack {
+    /// Time of the first hit, in milliseconds since the start of the encounter.
+    pub start_time: i64,
+    /// Time of the last hit, in milliseconds since the start of the encounter.
+    pub end_time: i64,
+    /// Player whose link attack hit first.
+    // @TODO: The game doesn't tell us who triggered link time, find out where it's decided to report that instead.
+    pub first_hit_by: u32,
+    /// Players that joined in, in the order they first hit.
+    pub participants: Vec<LinkParticipant>,
+    pub total_damage: u64,
+}
+
+/// Link attack stats of a player over the encounter.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerLinkAttacks {
+    pub index: u32,
+    /// Number of links the player hit first in.
+    pub first_hits: u32,
+    /// Number of links the player joined in, including the ones they hit first in.
+    pub participated: u32,
+    /// Percentage of the encounter's links that the player joined in.
+    pub participation: f64,
+    pub total_damage: u64,
+    pub average_damage: f64,
+}
+
+impl PlayerLinkAttacks {
+    fn new(index: u32) -> Self {
+        Self {
+            index,
+            first_hits: 0,
+            participated: 0,
+            participation: 0.0,
+            total_damage: 0,
+            average_damage: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkAttackStats {
+    pub links: Vec<LinkAttack>,
+    pub players: Vec<PlayerLinkAttacks>,
+}
+
+impl Parser {
+    /// Groups the link attack hits in the event log into links, only counting hits on the given targets.
+    /// Without a list of targets every target is counted. Every party member is listed, even if they never joined in.
+    pub fn link_attacks(&self, targets: Option<&[EnemyType]>) -> LinkAttackStats {
+        let start_time = self.start_time();
+        let mut links: Vec<LinkAttack> = Vec::new();
+
+        for (timestamp, message) in self.encounter.event_log() {
+            let Message::DamageEvent(event) = message else {
+                continue;
+            };
+
+            if !matches!(event.action_id, ActionType::LinkAttack) {
+                continue;
+            }
+
+            let target_type = EnemyType::from_hash(event.target.parent_actor_type);
+
+            if !is_targeted(targets, &target_type) {
+                continue;
+            }
+
+            let now = *timestamp - start_time;
+            let index = event.source.parent_index;
+            let damage = event.damage as u64;
+
+            let link = match links.last_mut() {
+                Some(link) if now - link.end_time <= LINK_WINDOW => link,
+                _ => {
+                    links.push(LinkAttack {
+                        start_time: now,
+                        end_time: now,
+                        first_hit_by: index,
+                        participants: Vec::new(),
+                        total_damage: 0,
+                    });
+                    links.last_mut().unwrap()
+                }
+            };
+
+            let participant = match link
+                .participants
+                .iter()
+                .position(|participant| participant.index == index)
+            {
+                Some(position) => &mut link.participants[position],
+                None => {
+                    link.participants.push(LinkParticipant {
+                        index,
+                        hits: 0,
+                        damage: 0,
+                    });
+                    link.participants.last_mut().unwrap()
+                }
+            };
+
+            participant.hits += 1;
+            participant.damage += damage;
+            link.total_damage += damage;
+            link.end_time = now;
+        }
+
+        let mut players: Vec<PlayerLinkAttacks> = self
+            .derived_state
+            .party
+            .values()
+            .map(|player| PlayerLinkAttacks::new(player.index))
+            .collect();
+
+        for link in links.iter() {
+            for participant in link.participants.iter() {
+                let player = match players
+                    .iter()
+                    .position(|player| player.index == participant.index)
+                {
+                    Some(position) => &mut players[position],
+                    None => {
+                        players.push(PlayerLinkAttacks::new(participant.index));
+                        players.last_mut().unwrap()
+                    }
+                };
+
+                if link.first_hit_by == participant.index {
+                    player.first_hits += 1;
+                }
+
+                player.participated += 1;
+                player.total_damage += participant.damage;
+            }
+        }
+
+        for player in players.iter_mut().filter(|player| player.participated > 0) {
+            player.participation = player.participated as f64 / links.len() as f64 * 100.0;
+            player.average_damage = player.total_damage as f64 / player.participated as f64;
+        }
+
+        players.sort_by_key(|player| player.index);
+
+        LinkAttackStats { links, players }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use protocol::{Actor, DamageEvent};
+
+    use super::*;
+    use crate::parser::{constants::CharacterType, v1::PlayerState};
+
+    fn link_hit(source_index: u32, damage: i32) -> Message {
+        let actor = |parent_index| Actor {
+            index: parent_index,
+            actor_type: 0,
+            parent_index,
+            parent_actor_type: 0,
+        };
+
+        Message::DamageEvent(DamageEvent {
+            source: actor(source_index),
+            target: actor(100),
+            damage,
+            flags: 0,
+            action_id: ActionType::LinkAttack,
+            attack_rate: None,
+            stun_value: None,
+            damage_cap: None,
+        })
+    }
+
+    #[test]
+    fn groups_hits_into_links() {
+        let mut parser = Parser::default();
+
+        // A party member that never joined in.
+        parser.derived_state.party.insert(
+            2,
+            PlayerState {
+                index: 2,
+                character_type: CharacterType::Pl0000,
+                total_damage: 0,
+                dps: 0.0,
+                sba: 0.0,
+                stun_per_second: 0.0,
+                total_stun_value: 0.0,
+                skill_breakdown: Vec::new(),
+                last_known_pet_skill: None,
+                normalized: None,
+                stats: None,
+            },
+        );
+
+        parser.encounter.raw_event_log = vec![
+            (1_000, link_hit(1, 100)),
+            (1_500, link_hit(0, 200)),
+            (2_000, link_hit(1, 100)),
+            (20_000, link_hit(0, 300)),
+        ];
+
+        let stats = parser.link_attacks(None);
+
+        assert_eq!(stats.links.len(), 2);
+        assert_eq!(stats.links[0].first_hit_by, 1);
+        assert_eq!(stats.links[0].end_time, 1_000);
+        assert_eq!(stats.links[0].total_damage, 400);
+        assert_eq!(
+            stats.links[0].participants,
+            vec![
+                LinkParticipant {
+                    index: 1,
+                    hits: 2,
+                    damage: 200,
+                },
+                LinkParticipant {
+                    index: 0,
+                    hits: 1,
+                    damage: 200,
+                },
+            ]
+        );
+        assert_eq!(
+            stats.players,
+            vec![
+                PlayerLinkAttacks {
+                    index: 0,
+                    first_hits: 1,
+                    participated: 2,
+                    participation: 100.0,
+                    total_damage: 500,
+                    average_damage: 250.0,
+                },
+                PlayerLinkAttacks {
+                    index: 1,
+                    first_hits: 1,
+                    participated: 1,
+                    participation: 50.0,
+                    total_damage: 200,
+                    average_damage: 200.0,
+                },
+                PlayerLinkAttacks::new(2),
+            ]
+        );
+    }
+}
@@ -2,23 +2,43 @@ use std::{collections::HashMap, io::BufReader};
 
 use anyhow::Result;
 use chrono::Utc;
+use log::warn;
 use protocol::{
-    AreaEnterEvent, DamageEvent, Message, OnAttemptSBAEvent, OnContinueSBAChainEvent, OnDeathEvent,
-    OnPerformSBAEvent, OnUpdateSBAEvent, PlayerLoadEvent, QuestCompleteEvent,
+    Actor, AreaEnterEvent, DamageEvent, EnemyConditionEvent, HookStatusEvent, Message,
+    MessagesDroppedEvent, OnAttemptSBAEvent, OnContinueSBAChainEvent, OnDeathEvent,
+    OnEnemyDeathEvent, OnPerformSBAEvent, OnUpdateSBAEvent, PlayerLoadEvent, QuestCompleteEvent,
+    QuestStartEvent, SkillCastEvent, StatusChangeEvent,
 };
 use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Manager, Window};
 
 use super::{
+    attribution,
     constants::{CharacterType, EnemyType},
+    equipment::{self, OvermasteryBonus, SigilInfo, WeaponTypeInfo, EMPTY_ID},
     v0,
 };
 
+mod build;
+mod condition_state;
+mod event_log;
+mod link_attack;
 mod player_state;
+mod rotation;
+mod sba;
 mod skill_state;
+mod status_state;
+mod transformation_state;
 
+pub use build::TraitTotal;
+use condition_state::ConditionState;
+pub use link_attack::{LinkAttack, LinkAttackStats, LinkParticipant, PlayerLinkAttacks};
 use player_state::PlayerState;
+pub use rotation::{PlayerRotation, SkillRotation};
+pub use sba::PlayerSba;
+use status_state::StatusState;
+use transformation_state::TransformationState;
 
 pub struct AdjustedDamageInstance<'a> {
     pub event: &'a DamageEvent,
@@ -38,6 +58,14 @@ impl<'a> AdjustedDamageInstance<'a> {
     }
 }
 
+/// Whether a target is counted, `None` counts every target.
+pub fn is_targeted(targets: Option<&[EnemyType]>, target: &EnemyType) -> bool {
+    match targets {
+        Some(targets) => targets.contains(target),
+        None => true,
+    }
+}
+
 /// Equippable sigil for a character
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -93,6 +121,24 @@ impl From<protocol::WeaponInfo> for WeaponInfo {
     }
 }
 
+impl WeaponInfo {
+    pub fn weapon_type(&self) -> Option<&'static WeaponTypeInfo> {
+        equipment::catalog().weapon(self.weapon_id)
+    }
+
+    /// Ids and levels of the traits on the weapon, skipping empty slots.
+    pub fn traits(&self) -> Vec<(u32, u32)> {
+        [
+            (self.trait_1_id, self.trait_1_level),
+            (self.trait_2_id, self.trait_2_level),
+            (self.trait_3_id, self.trait_3_level),
+        ]
+        .into_iter()
+        .filter(|&(id, level)| id != EMPTY_ID && level > 0)
+        .collect()
+    }
+}
+
 /// Overmastery, also known as `limit_bonus`.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -105,6 +151,15 @@ pub struct Overmastery {
     pub value: f32,
 }
 
+impl Overmastery {
+    /// The stat this overmastery raises, or `None` if it isn't in the catalog.
+    pub fn bonus(&self) -> Option<OvermasteryBonus> {
+        equipment::catalog()
+            .overmastery(self.id)
+            .map(|info| info.bonus(self.value))
+    }
+}
+
 impl From<protocol::Overmastery> for Overmastery {
     fn from(info: protocol::Overmastery) -> Self {
         Self {
@@ -133,7 +188,7 @@ impl From<protocol::OvermasteryInfo> for OvermasteryInfo {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct PlayerStats {
     pub level: u32,
@@ -181,6 +236,23 @@ struct Sigil {
     pub notification_enum: u32,
 }
 
+impl Sigil {
+    pub fn sigil_type(&self) -> Option<&'static SigilInfo> {
+        equipment::catalog().sigil(self.sigil_id)
+    }
+
+    /// Ids and levels of the traits on the sigil, skipping empty slots.
+    pub fn traits(&self) -> Vec<(u32, u32)> {
+        [
+            (self.first_trait_id, self.first_trait_level),
+            (self.second_trait_id, self.second_trait_level),
+        ]
+        .into_iter()
+        .filter(|&(id, level)| id != EMPTY_ID && level > 0)
+        .collect()
+    }
+}
+
 /// Data for a player in the encounter
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -197,12 +269,42 @@ pub struct PlayerData {
     sigils: Vec<Sigil>,
     /// Whether this player was an online player or not
     is_online: bool,
+    /// Party slot of this player, as reported by the game
+    #[serde(default)]
+    party_index: u8,
     /// Weapon info for this player
     weapon_info: Option<WeaponInfo>,
     /// Overmastery info for this player
     overmastery_info: Option<OvermasteryInfo>,
     /// Player stats for this player
     player_stats: Option<PlayerStats>,
+    /// Total level of each trait across this player's sigils and weapon, recomputed when loaded.
+    #[serde(default, skip_deserializing)]
+    trait_totals: Vec<TraitTotal>,
+}
+
+impl PlayerData {
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    pub fn character_type(&self) -> CharacterType {
+        self.character_type
+    }
+
+    /// Total level of each trait across this player's sigils and weapon.
+    pub fn trait_totals(&self) -> &[TraitTotal] {
+        &self.trait_totals
+    }
+
+    /// Fingerprint of this player's weapon, sigils and overmasteries, the same gear always has the same fingerprint.
+    pub fn loadout_fingerprint(&self) -> String {
+        build::loadout_fingerprint(self)
+    }
+
+    fn update_trait_totals(&mut self) {
+        self.trait_totals = build::trait_totals(&self.sigils, self.weapon_info.as_ref());
+    }
 }
 
 /// Derived breakdown for an enemy target
@@ -213,14 +315,124 @@ struct EnemyState {
     target_type: EnemyType,
     raw_target_type: u32,
     total_damage: u64,
+    /// Stun windows of this target.
+    #[serde(default)]
+    conditions: Vec<ConditionState>,
+    /// Timestamp of when this target died, if it did.
+    #[serde(default)]
+    killed_at: Option<i64>,
+    /// Uptime of the debuffs applied to this target.
+    #[serde(default)]
+    debuffs: Vec<StatusState>,
 }
 
 impl EnemyState {
+    fn new(actor: &Actor) -> Self {
+        Self {
+            index: actor.parent_index,
+            target_type: EnemyType::from_hash(actor.parent_actor_type),
+            raw_target_type: actor.parent_actor_type,
+            total_damage: 0,
+            conditions: Vec::new(),
+            killed_at: None,
+            debuffs: Vec::new(),
+        }
+    }
+
     fn update_from_damage_event(&mut self, damage_instance: &AdjustedDamageInstance) {
-        self.total_damage += damage_instance.event.damage as u64;
+        let damage = damage_instance.event.damage as u64;
+
+        self.total_damage += damage;
+
+        for condition in self.conditions.iter_mut() {
+            condition.add_damage(damage);
+        }
+    }
+
+    /// Returns false for an end without a start, as it has nothing to end.
+    fn update_from_condition_event(&mut self, now: i64, event: &EnemyConditionEvent) -> bool {
+        if !event.active {
+            return match self
+                .conditions
+                .iter_mut()
+                .find(|state| state.condition == event.condition && state.is_active())
+            {
+                Some(condition) => {
+                    condition.end(now);
+                    true
+                }
+                None => false,
+            };
+        }
+
+        let condition = match self
+            .conditions
+            .iter_mut()
+            .position(|state| state.condition == event.condition)
+        {
+            Some(position) => &mut self.conditions[position],
+            None => {
+                self.conditions.push(ConditionState::new(event.condition));
+                self.conditions.last_mut().unwrap()
+            }
+        };
+
+        condition.start(now, event.trigger.as_ref().map(|actor| actor.parent_index));
+        true
+    }
+}
+
+/// How the quest of an encounter ended, as reported by the game.
+/// Only completions are reported so far, retries, fails and retires are `Unknown` until the hook can detect them.
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, Clone, Copy)]
+pub enum QuestOutcome {
+    /// The quest is still in progress, or the encounter ended without the game reporting an outcome.
+    #[default]
+    Unknown,
+    Completed,
+}
+
+impl QuestOutcome {
+    /// Name stored in the `quest_outcome` column of the logs table.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuestOutcome::Unknown => "Unknown",
+            QuestOutcome::Completed => "Completed",
+        }
     }
 }
 
+/// A party member's presence during an encounter.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RosterEntry {
+    pub actor_index: u32,
+    pub party_index: u8,
+    pub is_online: bool,
+    /// When the player joined, or when the encounter started if they were already in the party.
+    pub joined_at: i64,
+}
+
+/// A party member's gear and stats as they were loaded during an encounter.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadoutSnapshot {
+    pub timestamp: i64,
+    pub player: PlayerData,
+}
+
+/// A party member whose stats or gear changed when they were loaded again during an encounter.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerStatChange {
+    pub actor_index: u32,
+    pub timestamp: i64,
+    pub before: Option<PlayerStats>,
+    pub after: Option<PlayerStats>,
+    /// Whether the weapon, sigils or overmasteries changed as well.
+    pub loadout_changed: bool,
+}
+
 /// The necessary details of an encounter that can be used to recreate the state at any point in time.
 #[derive(Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -230,15 +442,59 @@ pub struct Encounter {
     pub quest_timer: Option<u32>,
     #[serde(default)]
     pub quest_completed: bool,
+    #[serde(default)]
+    pub quest_outcome: QuestOutcome,
+    /// Difficulty of the quest, if the hook could read it.
+    #[serde(default)]
+    pub quest_difficulty: Option<u32>,
 
     /// DEPRECATED: Use `self.event_log()` instead.
     pub event_log: Vec<(i64, DamageEvent)>,
 
-    #[serde(default)]
+    /// Messages from newer versions of the app are kept as `Message::Unknown`.
+    #[serde(default, with = "event_log")]
     pub raw_event_log: Vec<(i64, Message)>,
+
+    /// Number of events from the hook that were lost during this encounter, the data is incomplete if non-zero.
+    #[serde(default)]
+    pub missing_events: u64,
+
+    /// Who was in the party during the encounter, and when they joined.
+    #[serde(default)]
+    pub roster: Vec<RosterEntry>,
+
+    /// Every time a party member was loaded during the encounter, starting with the party at the start.
+    #[serde(default)]
+    pub loadouts: Vec<LoadoutSnapshot>,
 }
 
 impl Encounter {
+    /// Actor index of the party member that a transformation loading in as its own player belongs to. It's matched
+    /// by actor index or party slot, falling back to the only party member that can turn into it.
+    fn transformation_parent(&self, event: &PlayerLoadEvent) -> Option<u32> {
+        let parent = attribution::rules()
+            .transformation_parent(CharacterType::from_hash(event.character_type))?;
+
+        let candidates: Vec<&PlayerData> = self
+            .player_data
+            .iter()
+            .flatten()
+            .filter(|player| player.character_type == parent)
+            .collect();
+
+        candidates
+            .iter()
+            .find(|player| {
+                player.actor_index == event.actor_index || player.party_index == event.party_index
+            })
+            .or(if candidates.len() == 1 {
+                candidates.first()
+            } else {
+                None
+            })
+            .map(|player| player.actor_index)
+    }
+
     /// Compresses this encounter data into a binary blob.
     pub fn to_blob(&self) -> Result<Vec<u8>> {
         let blob = cbor4ii::serde::to_vec(Vec::new(), &self)?;
@@ -250,7 +506,14 @@ impl Encounter {
     /// Deserializes a binary blob into encounter instance.
     pub fn from_blob(blob: &[u8]) -> Result<Self> {
         let decompressed = zstd::decode_all(blob)?;
-        Ok(cbor4ii::serde::from_slice(&decompressed)?)
+        let mut encounter: Self = cbor4ii::serde::from_slice(&decompressed)?;
+
+        // Logs saved before the outcome was stored only know whether the quest was completed.
+        if encounter.quest_completed && encounter.quest_outcome == QuestOutcome::Unknown {
+            encounter.quest_outcome = QuestOutcome::Completed;
+        }
+
+        Ok(encounter)
     }
 
     /// For older logs that don't have the event log, we need to repopulate it.
@@ -269,6 +532,76 @@ impl Encounter {
         self.player_data[0..=3].clone_from_slice(&[None, None, None, None]);
     }
 
+    /// Starts the roster over with the players that are currently loaded.
+    fn reset_roster(&mut self, now: i64) {
+        self.roster = self
+            .player_data
+            .iter()
+            .flatten()
+            .map(|player| RosterEntry {
+                actor_index: player.actor_index,
+                party_index: player.party_index,
+                is_online: player.is_online,
+                joined_at: now,
+            })
+            .collect();
+    }
+
+    /// Starts the loadout timeline over with the players that are currently loaded.
+    fn reset_loadouts(&mut self, now: i64) {
+        self.loadouts = self
+            .player_data
+            .iter()
+            .flatten()
+            .map(|player| LoadoutSnapshot {
+                timestamp: now,
+                player: player.clone(),
+            })
+            .collect();
+    }
+
+    /// Changes in each party member's stats or gear between consecutive loads, in the order they happened.
+    pub fn stat_changes(&self) -> Vec<PlayerStatChange> {
+        let mut previous: HashMap<u32, &PlayerData> = HashMap::new();
+        let mut changes = Vec::new();
+
+        for snapshot in &self.loadouts {
+            let player = &snapshot.player;
+
+            if let Some(before) = previous.insert(player.actor_index, player) {
+                let loadout_changed = before.loadout_fingerprint() != player.loadout_fingerprint();
+
+                if loadout_changed || before.player_stats != player.player_stats {
+                    changes.push(PlayerStatChange {
+                        actor_index: player.actor_index,
+                        timestamp: snapshot.timestamp,
+                        before: before.player_stats.clone(),
+                        after: player.player_stats.clone(),
+                        loadout_changed,
+                    });
+                }
+            }
+        }
+
+        changes
+    }
+
+    fn roster_join(&mut self, now: i64, actor_index: u32, party_index: u8, is_online: bool) {
+        let is_present = self
+            .roster
+            .iter()
+            .any(|entry| entry.actor_index == actor_index);
+
+        if !is_present {
+            self.roster.push(RosterEntry {
+                actor_index,
+                party_index,
+                is_online,
+                joined_at: now,
+            });
+        }
+    }
+
     fn reset_quest(&mut self) {
         self.quest_id = None;
         self.quest_timer = None;
@@ -315,6 +648,15 @@ pub struct DerivedEncounterState {
     pub party: HashMap<u32, PlayerState>,
     /// Derived target stats, damage done to each target.
     targets: HashMap<u32, EnemyState>,
+    /// Uptime of the buffs on each party member.
+    #[serde(default)]
+    buffs: HashMap<u32, Vec<StatusState>>,
+    /// Number of events that were lost, the encounter has incomplete data if non-zero.
+    #[serde(default)]
+    missing_events: u64,
+    /// Transformation uptime of each party member that can transform.
+    #[serde(default)]
+    transformations: HashMap<u32, TransformationState>,
 }
 
 impl Default for DerivedEncounterState {
@@ -329,6 +671,9 @@ impl Default for DerivedEncounterState {
             status: ParserStatus::Waiting,
             party: HashMap::new(),
             targets: HashMap::new(),
+            buffs: HashMap::new(),
+            missing_events: 0,
+            transformations: HashMap::new(),
         }
     }
 }
@@ -348,11 +693,116 @@ impl DerivedEncounterState {
         self.end_time = now;
     }
 
-    /// Gets the primary target of the encounter (the target that had the most damage done to it)
+    /// Gets the primary target of the encounter (the boss that had the most damage done to it),
+    /// falling back to any target if no bosses were hit.
     fn get_primary_target(&self) -> Option<&EnemyState> {
         self.targets
             .values()
+            .filter(|target| target.target_type.is_boss())
             .max_by_key(|target| target.total_damage)
+            .or_else(|| {
+                self.targets
+                    .values()
+                    .max_by_key(|target| target.total_damage)
+            })
+    }
+
+    fn target_entry(&mut self, actor: &Actor) -> &mut EnemyState {
+        self.targets
+            .entry(actor.parent_index)
+            .or_insert_with(|| EnemyState::new(actor))
+    }
+
+    /// Returns whether the event changed a condition. The hook sends every stun gauge change it sees, so
+    /// conditions on players and ends without a start are left out here.
+    fn process_condition_event(&mut self, now: i64, event: &EnemyConditionEvent) -> bool {
+        // Only enemies have conditions.
+        if !matches!(
+            CharacterType::from_hash(event.target.parent_actor_type),
+            CharacterType::Unknown(_)
+        ) {
+            return false;
+        }
+
+        if event.active {
+            self.target_entry(&event.target)
+                .update_from_condition_event(now, event)
+        } else {
+            self.targets
+                .get_mut(&event.target.parent_index)
+                .is_some_and(|target| target.update_from_condition_event(now, event))
+        }
+    }
+
+    /// Starts a transformation window for a party member, transforming before the first hit counts from it.
+    fn start_transformation(&mut self, now: i64, index: u32) {
+        self.transformations
+            .entry(index)
+            .or_insert_with(|| TransformationState::new(index))
+            .start(now.max(self.start_time));
+    }
+
+    /// Records the kill time of a target that was damaged in this encounter.
+    fn process_enemy_death(&mut self, now: i64, event: &OnEnemyDeathEvent) {
+        if let Some(target) = self.targets.get_mut(&event.actor_index) {
+            target.killed_at.get_or_insert(now);
+
+            for condition in target.conditions.iter_mut() {
+                condition.end(now);
+            }
+        }
+    }
+
+    /// Statuses on party members are buffs, statuses on anything else are debuffs on a target.
+    fn process_status_event(&mut self, now: i64, event: &StatusChangeEvent) {
+        let is_player = !matches!(
+            CharacterType::from_hash(event.target.parent_actor_type),
+            CharacterType::Unknown(_)
+        );
+
+        let statuses = if is_player {
+            self.buffs.entry(event.target.parent_index).or_default()
+        } else {
+            &mut self.target_entry(&event.target).debuffs
+        };
+
+        let status = match statuses
+            .iter()
+            .position(|status| status.status_id == event.status_id)
+        {
+            Some(position) => &mut statuses[position],
+            None => {
+                statuses.push(StatusState::new(event.status_id));
+                statuses.last_mut().unwrap()
+            }
+        };
+
+        status.update_from_event(now, event);
+    }
+
+    /// Ends the conditions and statuses that were still active when the encounter ended.
+    fn end_active_windows(&mut self) {
+        let end_time = self.end_time;
+
+        for target in self.targets.values_mut() {
+            for condition in target.conditions.iter_mut() {
+                condition.end(end_time);
+            }
+
+            for debuff in target.debuffs.iter_mut() {
+                debuff.end(end_time);
+            }
+        }
+
+        for buff in self.buffs.values_mut().flatten() {
+            buff.end(end_time);
+        }
+
+        let duration = self.duration();
+
+        for transformation in self.transformations.values_mut() {
+            transformation.update(end_time, duration);
+        }
     }
 
     fn process_damage_event(&mut self, now: i64, damage_instance: &AdjustedDamageInstance) {
@@ -380,23 +830,36 @@ impl DerivedEncounterState {
                 total_stun_value: 0.0,
                 skill_breakdown: Vec::new(),
                 last_known_pet_skill: None,
+                normalized: None,
+                stats: None,
             });
 
         // Update player stats from damage event.
         source_player.update_from_damage_event(damage_instance);
 
         // Update target stats from damage event.
-        let target = self
-            .targets
-            .entry(damage_instance.event.target.parent_index)
-            .or_insert(EnemyState {
-                index: damage_instance.event.target.parent_index,
-                target_type: EnemyType::from_hash(damage_instance.event.target.parent_actor_type),
-                raw_target_type: damage_instance.event.target.parent_actor_type,
-                total_damage: 0,
-            });
+        self.target_entry(&damage_instance.event.target)
+            .update_from_damage_event(damage_instance);
+
+        let source = &damage_instance.event.source;
+        let parent_type = CharacterType::from_hash(source.parent_actor_type);
+
+        if attribution::rules().has_transformation(parent_type) {
+            self.transformations
+                .entry(source.parent_index)
+                .or_insert_with(|| TransformationState::new(source.parent_index))
+                .add_damage(
+                    now,
+                    damage_instance.event.damage as u64,
+                    attribution::rules().is_transformation(parent_type, source.actor_type),
+                );
+        }
 
-        target.update_from_damage_event(damage_instance);
+        let duration = self.duration();
+
+        for transformation in self.transformations.values_mut() {
+            transformation.update(now, duration);
+        }
 
         // Update everyone's DPS
         for player in self.party.values_mut() {
@@ -405,6 +868,9 @@ impl DerivedEncounterState {
     }
 }
 
+/// Skill casts this long before the first hit are counted as part of the encounter, in milliseconds.
+const PENDING_EVENT_WINDOW: i64 = 10_000;
+
 /// The parser for the encounter.
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Parser {
@@ -426,6 +892,26 @@ pub struct Parser {
     /// The database connection for the parser, used to save the encounter
     #[serde(skip)]
     db: Option<Connection>,
+
+    /// Which hooks were installed in the game, as last reported by the hook
+    #[serde(skip)]
+    pub hook_status: Option<HookStatusEvent>,
+
+    /// Sequence number of the last event received from the hook, used to detect lost events
+    #[serde(skip)]
+    last_sequence: Option<u64>,
+
+    /// Events lost while no encounter was in progress, these are counted towards the next encounter in the same area
+    #[serde(skip)]
+    pending_missing_events: u64,
+
+    /// Events received while no encounter was in progress, the recent ones are added to the next encounter in the same area
+    #[serde(skip)]
+    pending_events: Vec<(i64, Message)>,
+
+    /// Set when entering an area, the party is dropped once the game starts loading it again
+    #[serde(skip)]
+    party_reload_pending: bool,
 }
 
 impl Parser {
@@ -439,8 +925,15 @@ impl Parser {
     }
 
     /// Peeks at the first damage event in the log to get the start time of the encounter.
+    /// The encounter starts at its first hit, events kept from right before it come earlier in the log.
     pub fn start_time(&self) -> i64 {
-        if let Some((timestamp, _)) = self.encounter.raw_event_log.first() {
+        let mut event_log = self.encounter.raw_event_log.iter();
+
+        if let Some((timestamp, _)) = event_log
+            .clone()
+            .find(|(_, event)| matches!(event, Message::DamageEvent(_)))
+            .or(event_log.next())
+        {
             *timestamp
         } else {
             1
@@ -448,7 +941,11 @@ impl Parser {
     }
 
     /// Reparses derived state from a given encounter.
-    pub fn from_encounter(encounter: Encounter) -> Self {
+    pub fn from_encounter(mut encounter: Encounter) -> Self {
+        for player in encounter.player_data.iter_mut().flatten() {
+            player.update_trait_totals();
+        }
+
         let mut parser = Self {
             encounter,
             ..Default::default()
@@ -471,6 +968,7 @@ impl Parser {
     pub fn reparse(&mut self) {
         self.derived_state = Default::default();
         self.derived_state.start(self.start_time());
+        self.derived_state.missing_events = self.encounter.missing_events;
 
         for (timestamp, event) in self.encounter.event_log() {
             self.derived_state.end_time = *timestamp;
@@ -490,26 +988,44 @@ impl Parser {
                     self.derived_state
                         .process_damage_event(*timestamp, &damage_instance);
                 }
+                Message::OnEnemyCondition(event) => {
+                    self.derived_state
+                        .process_condition_event(*timestamp, event);
+                }
+                Message::OnEnemyDeath(event) => {
+                    self.derived_state.process_enemy_death(*timestamp, event);
+                }
+                Message::PlayerLoadEvent(event) => {
+                    if let Some(index) = self.encounter.transformation_parent(event) {
+                        self.derived_state.start_transformation(*timestamp, index);
+                    }
+                }
+                Message::OnStatusChange(event) => {
+                    self.derived_state.process_status_event(*timestamp, event);
+                }
                 _ => {}
             }
         }
+
+        self.derived_state.end_active_windows();
     }
 
-    // Re-analyzes the encounter with the given targets.
-    pub fn reparse_with_options(&mut self, targets: &[EnemyType]) {
+    // Re-analyzes the encounter with the given targets, `None` counts every target.
+    pub fn reparse_with_options(&mut self, targets: Option<&[EnemyType]>) {
         self.derived_state = Default::default();
         self.derived_state.start(self.start_time());
+        self.derived_state.missing_events = self.encounter.missing_events;
 
         for (timestamp, event) in self.encounter.event_log() {
             self.derived_state.end_time = *timestamp;
 
             match event {
                 Message::DamageEvent(event) => {
-                    // If the target list is empty, then we're not filtering by target.
+                    // Without a target list we're not filtering by target.
                     // Otherwise, we only process damage events that match the target list.
                     let target_type = EnemyType::from_hash(event.target.parent_actor_type);
 
-                    if targets.is_empty() || targets.contains(&target_type) {
+                    if is_targeted(targets, &target_type) {
                         let player_data = self
                             .encounter
                             .player_data
@@ -524,9 +1040,38 @@ impl Parser {
                             .process_damage_event(*timestamp, &damage_instance);
                     }
                 }
+                Message::OnEnemyCondition(event) => {
+                    let target_type = EnemyType::from_hash(event.target.parent_actor_type);
+
+                    if is_targeted(targets, &target_type) {
+                        self.derived_state
+                            .process_condition_event(*timestamp, event);
+                    }
+                }
+                Message::OnEnemyDeath(event) => {
+                    self.derived_state.process_enemy_death(*timestamp, event);
+                }
+                Message::PlayerLoadEvent(event) => {
+                    if let Some(index) = self.encounter.transformation_parent(event) {
+                        self.derived_state.start_transformation(*timestamp, index);
+                    }
+                }
+                Message::OnStatusChange(event) => {
+                    let target_type = EnemyType::from_hash(event.target.parent_actor_type);
+                    let is_player = !matches!(
+                        CharacterType::from_hash(event.target.parent_actor_type),
+                        CharacterType::Unknown(_)
+                    );
+
+                    if is_player || is_targeted(targets, &target_type) {
+                        self.derived_state.process_status_event(*timestamp, event);
+                    }
+                }
                 _ => {}
             }
         }
+
+        self.derived_state.end_active_windows();
     }
 
     pub fn generate_sba_chart(&self, interval: i64) -> HashMap<u32, Vec<f32>> {
@@ -588,8 +1133,12 @@ impl Parser {
     /// If the current encounter was in progress, then stop it as we've left the instance.
     /// If there was damage in that stopped instance, then save it as a new log.
     /// Otherwise, we're waiting for the encounter to start.
+    /// The quest ID is set when a quest is loaded, and cleared when leaving the area of a quest that has ended.
+    /// If no quest load was seen, e.g. the parser connected mid-quest, the last known quest from the event is used.
     pub fn on_area_enter_event(&mut self, event: AreaEnterEvent) {
-        self.encounter.quest_id = Some(event.last_known_quest_id);
+        if self.encounter.quest_id.is_none() && event.last_known_quest_id != 0 {
+            self.encounter.quest_id = Some(event.last_known_quest_id);
+        }
 
         if self.status == ParserStatus::InProgress {
             self.update_status(ParserStatus::Stopped);
@@ -612,19 +1161,92 @@ impl Parser {
             self.update_status(ParserStatus::Waiting);
         }
 
+        if self.encounter.quest_outcome != QuestOutcome::Unknown {
+            self.encounter.quest_id = None;
+            self.encounter.quest_timer = None;
+        }
+
         self.encounter.quest_completed = false;
-        self.encounter.reset_player_data();
+        self.encounter.quest_outcome = QuestOutcome::Unknown;
+        // The game loads the party again after entering an area, so players that left are dropped then.
+        // @TODO: Drop players as they leave once the hook reports it.
+        self.party_reload_pending = true;
+        self.pending_missing_events = 0;
+        self.pending_events.clear();
 
         if let Some(window) = &self.window_handle {
             let _ = window.emit("on-area-enter", &self.derived_state);
         }
     }
 
+    /// Handles the event when a quest is loaded, before any of its areas are entered.
+    pub fn on_quest_start_event(&mut self, event: QuestStartEvent) {
+        self.encounter.quest_id = Some(event.quest_id);
+        self.encounter.quest_timer = None;
+        self.encounter.quest_completed = false;
+        self.encounter.quest_outcome = QuestOutcome::Unknown;
+        self.encounter.quest_difficulty = event.difficulty;
+    }
+
+    /// Records the quest as completed, then stops the encounter and saves it if it had damage.
     pub fn on_quest_complete_event(&mut self, event: QuestCompleteEvent) {
         self.encounter.quest_id = Some(event.quest_id);
         self.encounter.quest_timer = Some(event.elapsed_time_in_secs);
         self.encounter.quest_completed = true;
+        self.encounter.quest_outcome = QuestOutcome::Completed;
+
+        self.stop_encounter();
+    }
+
+    /// Handles an enemy dying during the encounter, recording when the target was killed.
+    // @TODO: Offer to end the encounter when the primary target dies, once the hook can report enemy deaths.
+    pub fn on_enemy_death_event(&mut self, event: OnEnemyDeathEvent) {
+        if self.status != ParserStatus::InProgress {
+            return;
+        }
+
+        let now = Utc::now().timestamp_millis();
 
+        self.derived_state.process_enemy_death(now, &event);
+        self.encounter.push_event(now, Message::OnEnemyDeath(event));
+
+        if let Some(window) = &self.window_handle {
+            let _ = window.emit("encounter-update", &self.derived_state);
+        }
+    }
+
+    /// Handles a skill being cast, only stored for building the rotation.
+    /// Casts right before an encounter, e.g. buffs before the first hit, are counted as part of it.
+    pub fn on_skill_cast_event(&mut self, event: SkillCastEvent) {
+        let now = Utc::now().timestamp_millis();
+
+        if self.status != ParserStatus::InProgress {
+            self.push_pending_event(now, Message::OnSkillCast(event));
+            return;
+        }
+
+        self.encounter.push_event(now, Message::OnSkillCast(event));
+    }
+
+    /// Handles a buff or debuff being applied or removed during the encounter.
+    pub fn on_status_change_event(&mut self, event: StatusChangeEvent) {
+        if self.status != ParserStatus::InProgress {
+            return;
+        }
+
+        let now = Utc::now().timestamp_millis();
+
+        self.derived_state.process_status_event(now, &event);
+        self.encounter
+            .push_event(now, Message::OnStatusChange(event));
+
+        if let Some(window) = &self.window_handle {
+            let _ = window.emit("encounter-update", &self.derived_state);
+        }
+    }
+
+    /// Stops the encounter in progress and saves it if there was damage.
+    fn stop_encounter(&mut self) {
         if self.status == ParserStatus::InProgress {
             self.update_status(ParserStatus::Stopped);
 
@@ -660,8 +1282,32 @@ impl Parser {
         // If this is the first damage event, set the start time.
         if self.status == ParserStatus::Stopped || self.status == ParserStatus::Waiting {
             self.reset();
+            self.encounter.reset_roster(now);
+            self.encounter.reset_loadouts(now);
             self.derived_state.start(now);
             self.update_status(ParserStatus::InProgress);
+
+            // Events lost right before the encounter started may have been part of it.
+            let pending_missing_events = std::mem::take(&mut self.pending_missing_events);
+            self.add_missing_events(pending_missing_events);
+
+            // Events from right before the first hit are kept at the time they happened.
+            for (timestamp, message) in std::mem::take(&mut self.pending_events) {
+                if now - timestamp > PENDING_EVENT_WINDOW {
+                    continue;
+                }
+
+                match &message {
+                    Message::PlayerLoadEvent(event) => {
+                        if let Some(index) = self.encounter.transformation_parent(event) {
+                            self.derived_state.start_transformation(timestamp, index);
+                        }
+                    }
+                    _ => {}
+                }
+
+                self.encounter.push_event(timestamp, message);
+            }
         }
 
         self.encounter
@@ -684,14 +1330,47 @@ impl Parser {
         }
     }
 
+    /// Handles an enemy entering or leaving a stun during the encounter.
+    /// Only events that changed a condition are saved, the rest are noise from the hook.
+    pub fn on_enemy_condition_event(&mut self, event: EnemyConditionEvent) {
+        if self.status != ParserStatus::InProgress {
+            return;
+        }
+
+        let now = Utc::now().timestamp_millis();
+
+        if !self.derived_state.process_condition_event(now, &event) {
+            return;
+        }
+
+        self.encounter
+            .push_event(now, Message::OnEnemyCondition(event));
+
+        if let Some(window) = &self.window_handle {
+            let _ = window.emit("encounter-update", &self.derived_state);
+        }
+    }
+
     pub fn on_player_load_event(&mut self, event: PlayerLoadEvent) {
         let character_type = CharacterType::from_hash(event.character_type);
 
-        // Ignore Id's transformation.
-        if character_type == CharacterType::Pl2000 {
+        // Transformations that load in as their own player (e.g. Id's dragon form) aren't party members, but they
+        // start a transformation of the player they belong to.
+        if attribution::rules().ignores_player_load(character_type) {
+            if attribution::rules()
+                .transformation_parent(character_type)
+                .is_some()
+            {
+                self.on_transformation_load(event);
+            }
+
             return;
         }
 
+        if std::mem::take(&mut self.party_reload_pending) {
+            self.encounter.reset_player_data();
+        }
+
         let sigils = event
             .sigils
             .into_iter()
@@ -708,18 +1387,22 @@ impl Parser {
             })
             .collect();
 
-        let player_data = PlayerData {
+        let mut player_data = PlayerData {
             actor_index: event.actor_index,
             display_name: event.display_name.to_string_lossy().to_string(),
             character_name: event.character_name.to_string_lossy().to_string(),
             is_online: event.is_online,
+            party_index: event.party_index,
             character_type,
             sigils,
             weapon_info: Some(event.weapon_info.into()),
             overmastery_info: Some(event.overmastery_info.into()),
             player_stats: Some(event.player_stats.into()),
+            trait_totals: Vec::new(),
         };
 
+        player_data.update_trait_totals();
+
         // Insert into encounter player data array, using actor_index.
         if !player_data.is_online && event.party_index == 0 {
             self.encounter.player_data[0] = Some(player_data.clone());
@@ -746,6 +1429,18 @@ impl Parser {
             }
         }
 
+        if self.status == ParserStatus::InProgress {
+            let now = Utc::now().timestamp_millis();
+
+            self.encounter
+                .roster_join(now, event.actor_index, event.party_index, event.is_online);
+
+            self.encounter.loadouts.push(LoadoutSnapshot {
+                timestamp: now,
+                player: player_data,
+            });
+        }
+
         if let Some(window) = &self.window_handle {
             let _ = window.emit("encounter-party-update", &self.encounter.player_data);
         }
@@ -824,7 +1519,89 @@ impl Parser {
         );
     }
 
+    /// Hook installation report, sent by the hook when the parser connects.
+    pub fn on_hook_status(&mut self, event: HookStatusEvent) {
+        for hook in event.failed() {
+            warn!(
+                "Hook {} is not installed: {}",
+                hook.name,
+                hook.error.as_deref().unwrap_or("unknown error")
+            );
+        }
+
+        if let Some(app) = &self.app {
+            let _ = app.emit_all("hook-status", &event);
+        }
+
+        self.hook_status = Some(event);
+    }
+
+    /// Checks the sequence number of an event from the hook, marking the encounter as incomplete if events were skipped.
+    pub fn on_sequence(&mut self, sequence: u64) {
+        if let Some(last_sequence) = self.last_sequence {
+            let missing = sequence.saturating_sub(last_sequence + 1);
+
+            if missing > 0 {
+                warn!("Missed {} events from the hook", missing);
+
+                if self.status == ParserStatus::InProgress {
+                    self.add_missing_events(missing);
+
+                    if let Some(window) = &self.window_handle {
+                        let _ = window.emit("encounter-update", &self.derived_state);
+                    }
+                } else {
+                    self.pending_missing_events += missing;
+                }
+            }
+        }
+
+        self.last_sequence = Some(sequence);
+    }
+
+    /// The hook couldn't keep up with sending events, the lost events themselves are detected through `on_sequence`.
+    pub fn on_messages_dropped(&mut self, event: MessagesDroppedEvent) {
+        warn!(
+            "Hook dropped {} messages ({} total)",
+            event.dropped, event.total_dropped
+        );
+    }
+
+    fn add_missing_events(&mut self, count: u64) {
+        self.encounter.missing_events += count;
+        self.derived_state.missing_events = self.encounter.missing_events;
+    }
+
+    /// Handles a transformation loading in as its own player, which starts a transformation window.
+    fn on_transformation_load(&mut self, event: PlayerLoadEvent) {
+        let now = Utc::now().timestamp_millis();
+
+        if self.status != ParserStatus::InProgress {
+            self.push_pending_event(now, Message::PlayerLoadEvent(event));
+            return;
+        }
+
+        if let Some(index) = self.encounter.transformation_parent(&event) {
+            self.derived_state.start_transformation(now, index);
+        }
+
+        self.encounter
+            .push_event(now, Message::PlayerLoadEvent(event));
+
+        if let Some(window) = &self.window_handle {
+            let _ = window.emit("encounter-update", &self.derived_state);
+        }
+    }
+
+    /// Keeps an event for the next encounter, dropping the ones too old to be part of it.
+    fn push_pending_event(&mut self, now: i64, message: Message) {
+        self.pending_events
+            .retain(|(timestamp, _)| now - timestamp <= PENDING_EVENT_WINDOW);
+        self.pending_events.push((now, message));
+    }
+
     fn reset(&mut self) {
+        self.encounter.missing_events = 0;
         self.encounter.raw_event_log.clear();
         self.encounter.raw_event_log.shrink_to_fit();
         self.derived_state = Default::default();
@@ -861,6 +1638,23 @@ impl Parser {
         false
     }
 
+    /// Loadout fingerprint and DPS of the player in each party slot, as they are stored with a log.
+    pub fn party_loadouts(&self) -> [(Option<String>, Option<f64>); 4] {
+        self.encounter.player_data.each_ref().map(|player| {
+            let Some(player) = player else {
+                return (None, None);
+            };
+
+            let dps = self
+                .derived_state
+                .party
+                .get(&player.actor_index)
+                .map(|player| player.dps);
+
+            (Some(player.loadout_fingerprint()), dps)
+        })
+    }
+
     fn save_encounter_to_db(&mut self) -> Result<Option<i64>> {
         let duration_in_millis = self.derived_state.duration();
         let start_datetime = self.derived_state.utc_start_time()?;
@@ -883,6 +1677,9 @@ impl Parser {
         let p3 = self.encounter.player_data[2].as_ref();
         let p4 = self.encounter.player_data[3].as_ref();
 
+        let [(p1_loadout, p1_dps), (p2_loadout, p2_dps), (p3_loadout, p3_dps), (p4_loadout, p4_dps)] =
+            self.party_loadouts();
+
         if let Some(conn) = &mut self.db {
             conn.execute(
                 r#"INSERT INTO logs (
@@ -902,8 +1699,17 @@ impl Parser {
                         p4_type,
                         quest_id,
                         quest_elapsed_time,
-                        quest_completed
-                    ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+                        quest_completed,
+                        quest_outcome,
+                        p1_loadout,
+                        p1_dps,
+                        p2_loadout,
+                        p2_dps,
+                        p3_loadout,
+                        p3_dps,
+                        p4_loadout,
+                        p4_dps
+                    ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
                 params![
                     "",
                     start_datetime.timestamp_millis(),
@@ -921,7 +1727,16 @@ impl Parser {
                     p4.map(|p| p.character_type.to_string()),
                     self.encounter.quest_id,
                     self.encounter.quest_timer,
-                    self.encounter.quest_completed
+                    self.encounter.quest_completed,
+                    self.encounter.quest_outcome.as_str(),
+                    p1_loadout,
+                    p1_dps,
+                    p2_loadout,
+                    p2_dps,
+                    p3_loadout,
+                    p3_dps,
+                    p4_loadout,
+                    p4_dps
                 ],
             )?;
 
@@ -1053,4 +1868,476 @@ mod tests {
         assert_eq!(parser.derived_state.end_time, 5_000);
         assert_eq!(parser.derived_state.duration(), 4_000);
     }
+
+    fn player_damage_event() -> DamageEvent {
+        DamageEvent {
+            source: Actor {
+                index: 0,
+                actor_type: 0x2AF678E8,
+                parent_actor_type: 0xFBA6615D,
+                parent_index: 0,
+            },
+            target: Actor {
+                index: 1,
+                actor_type: 0,
+                parent_actor_type: 0,
+                parent_index: 1,
+            },
+            damage: 100,
+            flags: 0,
+            action_id: ActionType::Normal(0),
+            attack_rate: None,
+            stun_value: None,
+            damage_cap: None,
+        }
+    }
+
+    #[test]
+    fn sequence_gaps_mark_encounter_incomplete() {
+        let mut parser = Parser::default();
+
+        parser.on_sequence(0);
+        parser.on_damage_event(player_damage_event());
+        parser.on_sequence(1);
+        parser.on_damage_event(player_damage_event());
+        assert_eq!(parser.encounter.missing_events, 0);
+
+        parser.on_sequence(5);
+        parser.on_damage_event(player_damage_event());
+        assert_eq!(parser.encounter.missing_events, 3);
+        assert_eq!(parser.derived_state.missing_events, 3);
+    }
+
+    #[test]
+    fn sequence_gaps_before_encounter_count_towards_it() {
+        let mut parser = Parser::default();
+
+        parser.on_sequence(0);
+        parser.on_sequence(3);
+        assert_eq!(parser.encounter.missing_events, 0);
+
+        parser.on_damage_event(player_damage_event());
+        assert_eq!(parser.encounter.missing_events, 2);
+
+        // Entering a new area forgets about events lost while waiting.
+        let mut parser = Parser::default();
+
+        parser.on_sequence(0);
+        parser.on_sequence(3);
+        parser.on_area_enter_event(AreaEnterEvent {
+            last_known_quest_id: 0,
+            last_known_elapsed_time_in_secs: 0,
+        });
+        parser.on_damage_event(player_damage_event());
+        assert_eq!(parser.encounter.missing_events, 0);
+    }
+
+    #[test]
+    fn casts_before_the_first_hit_keep_their_time() {
+        let mut parser = Parser::default();
+        let cast_time = Utc::now().timestamp_millis() - 5_000;
+
+        parser.pending_events.push((
+            cast_time,
+            Message::OnSkillCast(SkillCastEvent {
+                source: player_damage_event().source,
+                skill_id: 1501,
+                cooldown: None,
+            }),
+        ));
+        parser.on_damage_event(player_damage_event());
+
+        let hit_time = parser.encounter.raw_event_log[1].0;
+
+        assert_eq!(parser.encounter.raw_event_log[0].0, cast_time);
+        assert_eq!(parser.start_time(), hit_time);
+        assert_eq!(parser.rotation()[0].timeline[0].0, cast_time - hit_time);
+    }
+
+    #[test]
+    fn quest_end_events_store_outcome() {
+        let mut parser = Parser::default();
+
+        parser.on_quest_start_event(QuestStartEvent {
+            quest_id: 0x401,
+            difficulty: Some(2),
+        });
+        parser.on_damage_event(player_damage_event());
+        parser.on_quest_complete_event(QuestCompleteEvent {
+            quest_id: 0x401,
+            elapsed_time_in_secs: 120,
+        });
+
+        assert_eq!(parser.status, ParserStatus::Stopped);
+        assert_eq!(parser.encounter.quest_outcome, QuestOutcome::Completed);
+        assert_eq!(parser.encounter.quest_difficulty, Some(2));
+        assert_eq!(parser.encounter.quest_timer, Some(120));
+        assert!(parser.encounter.quest_completed);
+
+        // Starting the quest again clears the previous outcome.
+        parser.on_quest_start_event(QuestStartEvent {
+            quest_id: 0x401,
+            difficulty: None,
+        });
+        assert_eq!(parser.encounter.quest_outcome, QuestOutcome::Unknown);
+        assert!(!parser.encounter.quest_completed);
+
+        parser.on_quest_complete_event(QuestCompleteEvent {
+            quest_id: 0x401,
+            elapsed_time_in_secs: 120,
+        });
+
+        // Leaving the quest's area forgets about it, so that fights outside of quests aren't saved under it.
+        parser.on_area_enter_event(AreaEnterEvent {
+            last_known_quest_id: 0x401,
+            last_known_elapsed_time_in_secs: 120,
+        });
+        assert_eq!(parser.encounter.quest_id, None);
+        assert_eq!(parser.encounter.quest_timer, None);
+    }
+
+    #[test]
+    fn area_enter_keeps_the_current_quest() {
+        let mut parser = Parser::default();
+
+        parser.on_quest_start_event(QuestStartEvent {
+            quest_id: 0x401,
+            difficulty: None,
+        });
+        parser.on_area_enter_event(AreaEnterEvent {
+            last_known_quest_id: 0x999,
+            last_known_elapsed_time_in_secs: 0,
+        });
+
+        assert_eq!(parser.encounter.quest_id, Some(0x401));
+    }
+
+    #[test]
+    fn area_enter_uses_the_last_known_quest_without_a_quest_load() {
+        let mut parser = Parser::default();
+
+        parser.on_area_enter_event(AreaEnterEvent {
+            last_known_quest_id: 0,
+            last_known_elapsed_time_in_secs: 0,
+        });
+        assert_eq!(parser.encounter.quest_id, None);
+
+        parser.on_area_enter_event(AreaEnterEvent {
+            last_known_quest_id: 0x401,
+            last_known_elapsed_time_in_secs: 30,
+        });
+        assert_eq!(parser.encounter.quest_id, Some(0x401));
+    }
+
+    #[test]
+    fn older_completed_logs_have_completed_outcome() {
+        let encounter = Encounter {
+            quest_completed: true,
+            ..Default::default()
+        };
+
+        let blob = encounter.to_blob().unwrap();
+        let encounter = Encounter::from_blob(&blob).unwrap();
+
+        assert_eq!(encounter.quest_outcome, QuestOutcome::Completed);
+    }
+
+    #[test]
+    fn stun_windows_are_tracked_per_target() {
+        let damage = player_damage_event();
+        let stun = |active| {
+            Message::OnEnemyCondition(EnemyConditionEvent {
+                target: damage.target.clone(),
+                condition: protocol::EnemyCondition::Stun,
+                active,
+                trigger: active.then(|| damage.source.clone()),
+            })
+        };
+
+        let mut parser = Parser::default();
+
+        parser.encounter.raw_event_log = vec![
+            (1000, Message::DamageEvent(damage.clone())),
+            (1000, stun(true)),
+            (2000, Message::DamageEvent(damage.clone())),
+            (3000, Message::DamageEvent(damage.clone())),
+            (4000, stun(false)),
+            (5000, Message::DamageEvent(damage.clone())),
+            (6000, stun(true)),
+            (7000, Message::DamageEvent(damage.clone())),
+        ];
+
+        parser.reparse();
+
+        let target = &parser.derived_state.targets[&1];
+        let stun = &target.conditions[0];
+
+        assert_eq!(target.conditions.len(), 1);
+
+        assert_eq!(target.total_damage, 500);
+        assert_eq!(stun.count, 2);
+        assert_eq!(stun.total_damage, 300);
+        // The second stun is still active when the encounter ends.
+        assert_eq!(stun.total_time, 4000);
+        assert_eq!(stun.windows[0].trigger_index, Some(0));
+        assert_eq!(stun.windows[1].end_time, Some(7000));
+    }
+
+    #[test]
+    fn stun_without_a_start_or_on_players_is_ignored() {
+        let damage = player_damage_event();
+        let stun = |target: &Actor, active| {
+            Message::OnEnemyCondition(EnemyConditionEvent {
+                target: target.clone(),
+                condition: protocol::EnemyCondition::Stun,
+                active,
+                trigger: None,
+            })
+        };
+
+        let mut parser = Parser::default();
+
+        parser.encounter.raw_event_log = vec![
+            (1000, Message::DamageEvent(damage.clone())),
+            (1000, stun(&damage.target, false)),
+            (2000, stun(&damage.source, true)),
+            (
+                3000,
+                stun(
+                    &Actor {
+                        index: 5,
+                        actor_type: 0,
+                        parent_actor_type: 0,
+                        parent_index: 5,
+                    },
+                    false,
+                ),
+            ),
+        ];
+
+        parser.reparse();
+
+        assert_eq!(parser.derived_state.targets.len(), 1);
+        assert!(parser.derived_state.targets[&1].conditions.is_empty());
+
+        // Live events that don't change a condition aren't saved.
+        let mut parser = Parser::default();
+
+        parser.on_damage_event(damage.clone());
+        parser.on_enemy_condition_event(EnemyConditionEvent {
+            target: damage.target.clone(),
+            condition: protocol::EnemyCondition::Stun,
+            active: false,
+            trigger: None,
+        });
+        parser.on_enemy_condition_event(EnemyConditionEvent {
+            target: damage.source.clone(),
+            condition: protocol::EnemyCondition::Stun,
+            active: true,
+            trigger: None,
+        });
+
+        assert_eq!(parser.encounter.event_log().count(), 1);
+    }
+
+    fn player_load_event(actor_index: u32, party_index: u8, total_attack: u32) -> PlayerLoadEvent {
+        PlayerLoadEvent {
+            sigils: Vec::new(),
+            character_name: std::ffi::CString::new("Gran").unwrap(),
+            display_name: std::ffi::CString::new("Gran").unwrap(),
+            character_type: 0x26A4848A,
+            party_index,
+            actor_index,
+            is_online: party_index != 0,
+            weapon_info: protocol::WeaponInfo {
+                weapon_id: 0,
+                star_level: 0,
+                plus_marks: 0,
+                awakening_level: 0,
+                trait_1_id: 0,
+                trait_1_level: 0,
+                trait_2_id: 0,
+                trait_2_level: 0,
+                trait_3_id: 0,
+                trait_3_level: 0,
+                wrightstone_id: 0,
+                weapon_level: 0,
+                weapon_hp: 0,
+                weapon_attack: 0,
+            },
+            overmastery_info: protocol::OvermasteryInfo {
+                overmasteries: Vec::new(),
+            },
+            player_stats: protocol::PlayerStats {
+                level: 100,
+                total_hp: 30000,
+                total_attack,
+                stun_power: 10.0,
+                critical_rate: 80.0,
+                total_power: 50000,
+            },
+        }
+    }
+
+    #[test]
+    fn players_loading_mid_encounter_join_the_roster() {
+        let mut parser = Parser::default();
+
+        parser.on_player_load_event(player_load_event(0, 0, 10000));
+        parser.on_damage_event(player_damage_event());
+        parser.on_player_load_event(player_load_event(10, 1, 10000));
+        // Reloading doesn't join again.
+        parser.on_player_load_event(player_load_event(10, 1, 12000));
+
+        assert_eq!(parser.encounter.roster.len(), 2);
+        assert_eq!(parser.encounter.roster[1].actor_index, 10);
+        assert!(parser.encounter.roster[1].joined_at >= parser.encounter.roster[0].joined_at);
+    }
+
+    #[test]
+    fn roster_uses_party_slots_and_party_is_kept_until_reloaded() {
+        let mut parser = Parser::default();
+
+        parser.on_player_load_event(player_load_event(0, 0, 10000));
+        parser.on_player_load_event(player_load_event(10, 2, 10000));
+        parser.on_damage_event(player_damage_event());
+
+        let slots: Vec<(u32, u8)> = parser
+            .encounter
+            .roster
+            .iter()
+            .map(|entry| (entry.actor_index, entry.party_index))
+            .collect();
+
+        assert_eq!(slots, vec![(0, 0), (10, 2)]);
+
+        parser.on_area_enter_event(AreaEnterEvent {
+            last_known_quest_id: 0,
+            last_known_elapsed_time_in_secs: 0,
+        });
+        assert_eq!(parser.encounter.player_data.iter().flatten().count(), 2);
+
+        // Only the players that are loaded again are kept.
+        parser.on_player_load_event(player_load_event(0, 0, 10000));
+        assert_eq!(parser.encounter.player_data.iter().flatten().count(), 1);
+    }
+
+    #[test]
+    fn transformations_start_when_loaded_and_end_at_the_next_normal_hit() {
+        let id_damage_event = |actor_type| DamageEvent {
+            source: Actor {
+                index: 0,
+                actor_type,
+                parent_actor_type: 0x8056ABCD,
+                parent_index: 0,
+            },
+            ..player_damage_event()
+        };
+
+        let mut parser = Parser::default();
+
+        parser.on_player_load_event(PlayerLoadEvent {
+            character_type: 0x8056ABCD,
+            ..player_load_event(0, 0, 10000)
+        });
+
+        // The dragon form loads in as its own player, in Id's party slot.
+        let dragon = PlayerLoadEvent {
+            character_type: 0xF5755C0E,
+            ..player_load_event(5, 0, 10000)
+        };
+
+        parser.encounter.raw_event_log = vec![
+            (0, Message::DamageEvent(id_damage_event(0x8056ABCD))),
+            (1_000, Message::PlayerLoadEvent(dragon)),
+            (2_000, Message::DamageEvent(id_damage_event(0xF5755C0E))),
+            (5_000, Message::DamageEvent(id_damage_event(0x8056ABCD))),
+            (10_000, Message::DamageEvent(id_damage_event(0x8056ABCD))),
+        ];
+        parser.reparse();
+
+        let transformation = &parser.derived_state.transformations[&0];
+
+        assert_eq!(parser.encounter.player_data.iter().flatten().count(), 1);
+        assert_eq!(transformation.windows.len(), 1);
+        assert_eq!(transformation.windows[0].start_time, 1_000);
+        assert_eq!(transformation.windows[0].end_time, 5_000);
+        assert_eq!(transformation.transformed_damage, 100);
+        assert_eq!(transformation.normal_damage, 300);
+        assert_eq!(transformation.uptime, 40.0);
+    }
+
+    #[test]
+    fn reloading_mid_encounter_records_stat_changes() {
+        let load = |total_attack| player_load_event(0, 0, total_attack);
+
+        let mut parser = Parser::default();
+
+        parser.on_player_load_event(load(10000));
+        parser.on_damage_event(player_damage_event());
+        parser.on_player_load_event(load(10000));
+        parser.on_player_load_event(load(12000));
+
+        assert_eq!(parser.encounter.loadouts.len(), 3);
+
+        let changes = parser.encounter.stat_changes();
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].before.as_ref().unwrap().total_attack, 10000);
+        assert_eq!(changes[0].after.as_ref().unwrap().total_attack, 12000);
+        assert!(!changes[0].loadout_changed);
+    }
+
+    #[test]
+    fn enemy_death_records_the_kill_time() {
+        let mut parser = Parser::default();
+
+        parser.on_damage_event(player_damage_event());
+        parser.on_enemy_death_event(OnEnemyDeathEvent {
+            actor_index: 2,
+            actor_type: 0,
+        });
+        parser.on_enemy_death_event(OnEnemyDeathEvent {
+            actor_index: 1,
+            actor_type: 0,
+        });
+
+        assert_eq!(parser.status, ParserStatus::InProgress);
+        assert!(parser.derived_state.targets[&1].killed_at.is_some());
+        assert!(!parser.derived_state.targets.contains_key(&2));
+    }
+
+    #[test]
+    fn statuses_are_split_into_buffs_and_debuffs() {
+        let damage = player_damage_event();
+        let status = |target: &Actor, active| {
+            Message::OnStatusChange(StatusChangeEvent {
+                source: Some(damage.source.clone()),
+                target: target.clone(),
+                status_id: 7,
+                skill_id: None,
+                active,
+            })
+        };
+
+        let mut parser = Parser::default();
+
+        parser.encounter.raw_event_log = vec![
+            (1000, Message::DamageEvent(damage.clone())),
+            (1000, status(&damage.source, true)),
+            (2000, status(&damage.target, true)),
+            (3000, status(&damage.source, false)),
+            (5000, Message::DamageEvent(damage.clone())),
+        ];
+
+        parser.reparse();
+
+        let buff = &parser.derived_state.buffs[&0][0];
+        let debuff = &parser.derived_state.targets[&1].debuffs[0];
+
+        assert_eq!(buff.total_time, 2000);
+        assert_eq!(buff.time_by_applier, vec![(0, 2000)]);
+        // The debuff is still active when the encounter ends.
+        assert_eq!(debuff.total_time, 3000);
+    }
 }
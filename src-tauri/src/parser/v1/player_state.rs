@@ -1,9 +1,9 @@
-use protocol::{ActionType, DamageEvent};
+use protocol::ActionType;
 use serde::{Deserialize, Serialize};
 
-use crate::parser::constants::{CharacterType, FerrySkillId};
+use crate::parser::{attribution, constants::CharacterType};
 
-use super::{skill_state::SkillState, AdjustedDamageInstance};
+use super::{skill_state::SkillState, AdjustedDamageInstance, PlayerStats};
 
 /// Derived stat breakdown for a player
 #[derive(Debug, Serialize, Deserialize)]
@@ -12,12 +12,40 @@ pub struct PlayerState {
     pub index: u32,
     pub character_type: CharacterType,
     pub total_damage: u64,
-    pub last_known_pet_skill: Option<ActionType>, // used for Ferry's skills that don't keep track of where they came from
+    pub last_known_pet_skill: Option<ActionType>, // used for pet skills that don't keep track of where they came from
     pub dps: f64,
     pub skill_breakdown: Vec<SkillState>,
     pub sba: f64,
     pub total_stun_value: f64,
     pub stun_per_second: f64,
+    /// Output relative to the player's stats, to compare players with different gear.
+    #[serde(default)]
+    pub normalized: Option<NormalizedMetrics>,
+    /// Stats of the player, if they were loaded before dealing damage.
+    #[serde(skip)]
+    pub stats: Option<PlayerStats>,
+}
+
+/// A player's output divided by their stats.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizedMetrics {
+    pub dps_per_1k_attack: f64,
+    pub dps_per_1k_power: f64,
+    /// Stun dealt per point of stun power (as stored by the game, the UI shows it multiplied by 10).
+    pub stun_per_stun_power: f64,
+}
+
+impl NormalizedMetrics {
+    fn new(dps: f64, total_stun_value: f64, stats: &PlayerStats) -> Self {
+        let per = |value: f64, stat: f64| if stat > 0.0 { value / stat } else { 0.0 };
+
+        Self {
+            dps_per_1k_attack: per(dps, stats.total_attack as f64 / 1000.0),
+            dps_per_1k_power: per(dps, stats.total_power as f64 / 1000.0),
+            stun_per_stun_power: per(total_stun_value, stats.stun_power as f64),
+        }
+    }
 }
 
 impl PlayerState {
@@ -28,66 +56,42 @@ impl PlayerState {
     pub fn update_dps(&mut self, now: i64, start_time: i64) {
         self.dps = self.total_damage as f64 / ((now - start_time) as f64 / 1000.0);
         self.stun_per_second = self.total_stun_value / ((now - start_time) as f64 / 1000.0);
-    }
-
-    // @todo(false): maybe Ferry specific stuff can be removed/abstracted if some extra flags are found or the attribution is fixed
-    pub fn get_action_from_ferry_damage_event(&mut self, event: &DamageEvent) -> ActionType {
-        // Ferry needs special handling because the action_id that comes back for pet skills is usually wrong
-        // e.g. if you strafe then dodge the action_id for further hits comes back as "dodge"
-        let is_ferry_pet =
-            CharacterType::Pl0700Ghost == CharacterType::from_hash(event.source.actor_type);
-        let is_ferry_pet_skill = is_ferry_pet && (event.flags & (1 << 2) != 0); // pet skills for ferry always have this flag set
-        let is_ferry_pet_normal =
-            is_ferry_pet && !is_ferry_pet_skill && event.action_id != ActionType::LinkAttack;
-
-        // Umlauf excluded since that uses a separate actor which works correctly
-        if is_ferry_pet_skill
-            && vec![
-                FerrySkillId::BlausGespenst,
-                FerrySkillId::Pendel,
-                FerrySkillId::Strafe,
-            ]
-            .into_iter()
-            .any(|skill_id| ActionType::Normal(skill_id as u32) == event.action_id)
-        {
-            self.last_known_pet_skill = Some(event.action_id);
-        }
-
-        const PET_NORMAL: ActionType = ActionType::Normal(FerrySkillId::PetNormal as u32);
-
-        if is_ferry_pet_normal {
-            // Note technically the pet portion of Onslaught will count as a Pet normal, but I think that's fine since
-            // it does exactly as much as a pet normal. Could consider adding Onslaught (pet) as a separate category
-            PET_NORMAL
-        } else if is_ferry_pet_skill {
-            match self.last_known_pet_skill {
-                None => PET_NORMAL, // May be good to instead have a separate "pet skill" backup for this case
-                Some(skill_id) => skill_id,
-            }
-        } else {
-            event.action_id
-        }
+        self.normalized = self
+            .stats
+            .as_ref()
+            .map(|stats| NormalizedMetrics::new(self.dps, self.total_stun_value, stats));
     }
 
     pub fn update_from_damage_event(&mut self, damage_instance: &AdjustedDamageInstance) {
         self.total_damage += damage_instance.event.damage as u64;
         self.total_stun_value += damage_instance.stun_damage;
 
+        if let Some(stats) = damage_instance
+            .player_data
+            .and_then(|player| player.player_stats.as_ref())
+        {
+            self.stats = Some(stats.clone());
+        }
+
         let parent_character_type =
             CharacterType::from_hash(damage_instance.event.source.parent_actor_type);
 
-        // @TODO(false): Collapse all skill IDs from Seofon's avatar into his own.
-        let child_character_type = if parent_character_type == CharacterType::Pl2200 {
-            parent_character_type
-        } else {
-            CharacterType::from_hash(damage_instance.event.source.actor_type)
+        // Pets and summons may need their hits merged into the parent or their action IDs fixed up.
+        let rule = attribution::rules().child_rule(
+            parent_character_type,
+            damage_instance.event.source.actor_type,
+        );
+
+        let child_character_type = match rule {
+            Some(rule) if rule.merge_into_parent => parent_character_type,
+            _ => CharacterType::from_hash(damage_instance.event.source.actor_type),
         };
 
-        // for ferry defer to special function to handle the weird way her pets work
-        let action = if parent_character_type == CharacterType::Pl0700 {
-            self.get_action_from_ferry_damage_event(damage_instance.event)
-        } else {
-            damage_instance.event.action_id
+        let action = match rule {
+            Some(rule) => {
+                rule.resolve_action(damage_instance.event, &mut self.last_known_pet_skill)
+            }
+            None => damage_instance.event.action_id,
         };
 
         // If the skill is already being tracked, update it.
@@ -119,6 +123,8 @@ impl PlayerState {
 
 #[cfg(test)]
 mod tests {
+    use protocol::DamageEvent;
+
     use crate::parser::v1::{PlayerData, PlayerStats};
 
     use super::*;
@@ -135,6 +141,8 @@ mod tests {
             sba: 0.0,
             total_stun_value: 0.0,
             stun_per_second: 0.0,
+            normalized: None,
+            stats: None,
         };
 
         player_state.update_dps(1000, 0);
@@ -154,6 +162,8 @@ mod tests {
             sba: 0.0,
             total_stun_value: 0.0,
             stun_per_second: 0.0,
+            normalized: None,
+            stats: None,
         };
 
         let damage_event = DamageEvent {
@@ -199,6 +209,8 @@ mod tests {
             sba: 0.0,
             total_stun_value: 0.0,
             stun_per_second: 0.0,
+            normalized: None,
+            stats: None,
         };
 
         let damage_event = DamageEvent {
@@ -252,6 +264,8 @@ mod tests {
             sba: 0.0,
             stun_per_second: 0.0,
             total_stun_value: 0.0,
+            normalized: None,
+            stats: None,
         };
 
         let skill_one = DamageEvent {
@@ -321,6 +335,8 @@ mod tests {
             sba: 0.0,
             stun_per_second: 0.0,
             total_stun_value: 0.0,
+            normalized: None,
+            stats: None,
         };
 
         let parent_skill = DamageEvent {
@@ -384,6 +400,55 @@ mod tests {
         assert_eq!(player_state.skill_breakdown[1].total_damage, 200);
     }
 
+    #[test]
+    fn seofon_avatar_skills_are_merged_into_parent() {
+        let mut player_state = PlayerState {
+            index: 0,
+            character_type: CharacterType::Pl2200,
+            total_damage: 0,
+            last_known_pet_skill: None,
+            dps: 0.0,
+            skill_breakdown: vec![],
+            sba: 0.0,
+            stun_per_second: 0.0,
+            total_stun_value: 0.0,
+            normalized: None,
+            stats: None,
+        };
+
+        let avatar_skill = DamageEvent {
+            source: protocol::Actor {
+                index: 1,
+                actor_type: 0x5B1AB457,
+                parent_actor_type: 0x59DB0CD9,
+                parent_index: 0,
+            },
+            target: protocol::Actor {
+                index: 0,
+                actor_type: 0,
+                parent_actor_type: 0,
+                parent_index: 0,
+            },
+            action_id: ActionType::Normal(1),
+            damage: 100,
+            flags: 0,
+            attack_rate: None,
+            stun_value: None,
+            damage_cap: None,
+        };
+
+        player_state.update_from_damage_event(&AdjustedDamageInstance::from_damage_event(
+            &avatar_skill,
+            None,
+        ));
+
+        assert_eq!(player_state.skill_breakdown.len(), 1);
+        assert_eq!(
+            player_state.skill_breakdown[0].child_character_type,
+            CharacterType::Pl2200
+        );
+    }
+
     #[test]
     fn stun_is_tracked_with_player_stats() {
         let mut player_state = PlayerState {
@@ -396,6 +461,8 @@ mod tests {
             sba: 0.0,
             total_stun_value: 0.0,
             stun_per_second: 0.0,
+            normalized: None,
+            stats: None,
         };
 
         let damage_event = DamageEvent {
@@ -426,6 +493,7 @@ mod tests {
             character_name: "Test".to_string(),
             sigils: Vec::new(),
             is_online: false,
+            party_index: 0,
             weapon_info: None,
             overmastery_info: None,
             player_stats: Some(PlayerStats {
@@ -436,6 +504,7 @@ mod tests {
                 critical_rate: 100.0,
                 total_power: 1000,
             }),
+            trait_totals: Vec::new(),
         };
 
         player_state.update_from_damage_event(&AdjustedDamageInstance::from_damage_event(
@@ -444,6 +513,17 @@ mod tests {
         ));
 
         assert_eq!(player_state.total_stun_value, 5.0);
+
+        player_state.update_dps(1000, 0);
+
+        assert_eq!(
+            player_state.normalized,
+            Some(NormalizedMetrics {
+                dps_per_1k_attack: 100.0,
+                dps_per_1k_power: 100.0,
+                stun_per_stun_power: 5.0 / 130.0,
+            })
+        );
     }
 
     #[test]
@@ -458,6 +538,8 @@ mod tests {
             sba: 0.0,
             total_stun_value: 0.0,
             stun_per_second: 0.0,
+            normalized: None,
+            stats: None,
         };
 
         let damage_event = DamageEvent {
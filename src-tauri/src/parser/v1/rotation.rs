@@ -0,0 +1,300 @@
+use protocol::{ActionType, Actor, Message};
+use serde::Serialize;
+
+use crate::parser::constants::CharacterType;
+
+use super::Parser;
+
+/// Hits of the same skill closer together than this are counted as one cast when there are no cast events.
+const HIT_CAST_GAP: i64 = 1500;
+
+/// Casts of a single skill by a player.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillRotation {
+    pub skill_id: u32,
+    pub casts: u32,
+    /// Time between consecutive casts in milliseconds.
+    pub intervals: Vec<i64>,
+    pub average_interval: Option<f64>,
+    pub shortest_interval: Option<i64>,
+    /// Cooldown reported by the game in milliseconds, if known.
+    pub cooldown: Option<i64>,
+    /// Average time the skill was ready but not cast, in milliseconds. Needs the cooldown to be known.
+    pub average_delay: Option<f64>,
+}
+
+impl SkillRotation {
+    fn new(skill_id: u32) -> Self {
+        Self {
+            skill_id,
+            casts: 0,
+            intervals: Vec::new(),
+            average_interval: None,
+            shortest_interval: None,
+            cooldown: None,
+            average_delay: None,
+        }
+    }
+
+    fn add_cast(&mut self, cooldown: Option<f32>, last_cast: Option<i64>, now: i64) {
+        self.casts += 1;
+
+        if let Some(cooldown) = cooldown {
+            self.cooldown = Some((cooldown * 1000.0) as i64);
+        }
+
+        if let Some(last_cast) = last_cast {
+            self.intervals.push(now - last_cast);
+        }
+    }
+
+    fn finish(&mut self) {
+        if self.intervals.is_empty() {
+            return;
+        }
+
+        let count = self.intervals.len() as f64;
+
+        self.average_interval = Some(self.intervals.iter().sum::<i64>() as f64 / count);
+        self.shortest_interval = self.intervals.iter().min().copied();
+        self.average_delay = self.cooldown.map(|cooldown| {
+            self.intervals
+                .iter()
+                .map(|interval| (interval - cooldown).max(0))
+                .sum::<i64>() as f64
+                / count
+        });
+    }
+}
+
+/// Skill casts of a player over the encounter.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerRotation {
+    pub index: u32,
+    pub character_type: CharacterType,
+    /// Every cast in order, as milliseconds since the start of the encounter and the skill ID.
+    pub timeline: Vec<(i64, u32)>,
+    pub skills: Vec<SkillRotation>,
+}
+
+/// A skill cast as the time it happened, who cast it, the skill ID and its cooldown in seconds if known.
+type Cast<'a> = (i64, &'a Actor, u32, Option<f32>);
+
+impl Parser {
+    /// Skill casts in the event log. Logs without cast events fall back to the skill hits in the damage events,
+    /// where a hit starts a new cast if the same skill didn't hit within the last [`HIT_CAST_GAP`].
+    fn casts(&self) -> Vec<Cast<'_>> {
+        let casts: Vec<Cast> = self
+            .encounter
+            .event_log()
+            .filter_map(|(timestamp, message)| match message {
+                Message::OnSkillCast(event) => {
+                    Some((*timestamp, &event.source, event.skill_id, event.cooldown))
+                }
+                _ => None,
+            })
+            .collect();
+
+        if !casts.is_empty() {
+            return casts;
+        }
+
+        let mut last_hits: Vec<(u32, u32, i64)> = Vec::new();
+        let mut casts = Vec::new();
+
+        for (timestamp, message) in self.encounter.event_log() {
+            let Message::DamageEvent(event) = message else {
+                continue;
+            };
+
+            let ActionType::Normal(skill_id) = event.action_id else {
+                continue;
+            };
+
+            let index = event.source.parent_index;
+
+            match last_hits.iter_mut().find(|(hit_index, hit_skill_id, _)| {
+                *hit_index == index && *hit_skill_id == skill_id
+            }) {
+                Some((_, _, last_hit)) => {
+                    if *timestamp - *last_hit > HIT_CAST_GAP {
+                        casts.push((*timestamp, &event.source, skill_id, None));
+                    }
+
+                    *last_hit = *timestamp;
+                }
+                None => {
+                    last_hits.push((index, skill_id, *timestamp));
+                    casts.push((*timestamp, &event.source, skill_id, None));
+                }
+            }
+        }
+
+        casts
+    }
+
+    /// Builds the rotation of each player from the skill casts in the event log, enemy casts are left out.
+    /// Casts from right before the first hit have a negative time.
+    pub fn rotation(&self) -> Vec<PlayerRotation> {
+        let start_time = self.start_time();
+        let mut rotations: Vec<PlayerRotation> = Vec::new();
+
+        for (timestamp, source, skill_id, cooldown) in self.casts() {
+            let character_type = CharacterType::from_hash(source.parent_actor_type);
+
+            if matches!(character_type, CharacterType::Unknown(_)) {
+                continue;
+            }
+
+            let index = source.parent_index;
+
+            let rotation = match rotations
+                .iter()
+                .position(|rotation| rotation.index == index)
+            {
+                Some(position) => &mut rotations[position],
+                None => {
+                    rotations.push(PlayerRotation {
+                        index,
+                        character_type,
+                        timeline: Vec::new(),
+                        skills: Vec::new(),
+                    });
+                    rotations.last_mut().unwrap()
+                }
+            };
+
+            let now = timestamp - start_time;
+
+            let last_cast = rotation
+                .timeline
+                .iter()
+                .rev()
+                .find(|(_, cast_skill_id)| *cast_skill_id == skill_id)
+                .map(|(time, _)| *time);
+
+            let skill = match rotation
+                .skills
+                .iter()
+                .position(|skill| skill.skill_id == skill_id)
+            {
+                Some(position) => &mut rotation.skills[position],
+                None => {
+                    rotation.skills.push(SkillRotation::new(skill_id));
+                    rotation.skills.last_mut().unwrap()
+                }
+            };
+
+            skill.add_cast(cooldown, last_cast, now);
+            rotation.timeline.push((now, skill_id));
+        }
+
+        for rotation in rotations.iter_mut() {
+            for skill in rotation.skills.iter_mut() {
+                skill.finish();
+            }
+        }
+
+        rotations.sort_by_key(|rotation| rotation.index);
+        rotations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use protocol::{DamageEvent, SkillCastEvent};
+
+    use super::*;
+
+    fn cast_by(parent_actor_type: u32, skill_id: u32, cooldown: Option<f32>) -> Message {
+        Message::OnSkillCast(SkillCastEvent {
+            source: Actor {
+                index: 0,
+                actor_type: 0,
+                parent_index: 0,
+                parent_actor_type,
+            },
+            skill_id,
+            cooldown,
+        })
+    }
+
+    fn cast(skill_id: u32, cooldown: Option<f32>) -> Message {
+        cast_by(0x26A4848A, skill_id, cooldown)
+    }
+
+    fn hit(action_id: ActionType) -> Message {
+        let actor = Actor {
+            index: 0,
+            actor_type: 0,
+            parent_index: 0,
+            parent_actor_type: 0x26A4848A,
+        };
+
+        Message::DamageEvent(DamageEvent {
+            source: actor.clone(),
+            target: actor,
+            damage: 100,
+            flags: 0,
+            action_id,
+            attack_rate: None,
+            stun_value: None,
+            damage_cap: None,
+        })
+    }
+
+    #[test]
+    fn builds_timeline_and_intervals_per_skill() {
+        let mut parser = Parser::default();
+
+        parser.encounter.raw_event_log = vec![
+            (1000, cast(1501, Some(10.0))),
+            (2000, cast(1201, None)),
+            (13000, cast(1501, Some(10.0))),
+            (23000, cast(1501, Some(10.0))),
+            // Enemies cast skills too, but they have no rotation.
+            (24000, cast_by(0, 1501, None)),
+        ];
+
+        let rotation = parser.rotation();
+        let armor_break = &rotation[0].skills[0];
+
+        assert_eq!(rotation.len(), 1);
+        assert_eq!(rotation[0].character_type, CharacterType::Pl0000);
+        assert_eq!(
+            rotation[0].timeline,
+            vec![(0, 1501), (1000, 1201), (12000, 1501), (22000, 1501)]
+        );
+        assert_eq!(armor_break.casts, 3);
+        assert_eq!(armor_break.intervals, vec![12000, 10000]);
+        assert_eq!(armor_break.shortest_interval, Some(10000));
+        assert_eq!(armor_break.average_delay, Some(1000.0));
+        assert_eq!(rotation[0].skills[1].average_interval, None);
+    }
+
+    #[test]
+    fn falls_back_to_skill_hits_without_casts() {
+        let mut parser = Parser::default();
+
+        parser.encounter.raw_event_log = vec![
+            (1000, hit(ActionType::Normal(1501))),
+            // Multi-hit skills count as one cast.
+            (1200, hit(ActionType::Normal(1501))),
+            (1400, hit(ActionType::LinkAttack)),
+            (2000, hit(ActionType::Normal(1201))),
+            (12000, hit(ActionType::Normal(1501))),
+        ];
+
+        let rotation = parser.rotation();
+
+        assert_eq!(
+            rotation[0].timeline,
+            vec![(0, 1501), (1000, 1201), (11000, 1501)]
+        );
+        assert_eq!(rotation[0].skills[0].casts, 2);
+        assert_eq!(rotation[0].skills[0].intervals, vec![11000]);
+        assert_eq!(rotation[0].skills[0].average_delay, None);
+    }
+}
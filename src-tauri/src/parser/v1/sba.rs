@@ -0,0 +1,257 @@
+use protocol::{ActionType, Message};
+use serde::Serialize;
+
+use super::Parser;
+
+/// Gauge value at which a Skybound Art can be used.
+const FULL_GAUGE: f32 = 1000.0;
+
+/// Skybound Art usage of a player over the encounter.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerSba {
+    pub index: u32,
+    /// Number of Skybound Arts performed.
+    pub sba_count: u32,
+    /// Number of times the player continued someone else's chain.
+    /// Only known for the local player, as the game doesn't send this for others.
+    pub chain_count: u32,
+    /// Time taken to fill the gauge from empty to full after each Skybound Art, in milliseconds.
+    pub fill_times: Vec<i64>,
+    pub average_fill_time: Option<f64>,
+    /// Time spent with a full gauge without using it, in milliseconds.
+    pub time_at_full: i64,
+    /// Percentage of the encounter's chains that the player took part in.
+    /// Chains are only known when the local player continues them, so chains without them aren't counted.
+    pub chain_participation: f64,
+    /// Damage done by Skybound Arts and chain bursts.
+    pub sba_damage: u64,
+}
+
+impl PlayerSba {
+    fn new(index: u32) -> Self {
+        Self {
+            index,
+            sba_count: 0,
+            chain_count: 0,
+            fill_times: Vec::new(),
+            average_fill_time: None,
+            time_at_full: 0,
+            chain_participation: 0.0,
+            sba_damage: 0,
+        }
+    }
+}
+
+/// Gauge tracking of a player while going through the event log.
+#[derive(Default)]
+struct GaugeTracker {
+    /// When the gauge was last emptied by using a Skybound Art.
+    empty_since: Option<i64>,
+    /// When the gauge became full, if it still is.
+    full_since: Option<i64>,
+}
+
+impl Parser {
+    /// Builds the Skybound Art stats of each player from the SBA events in the event log.
+    /// Damage is taken from the derived state, so it respects the targets the encounter was reparsed with.
+    pub fn sba_stats(&self) -> Vec<PlayerSba> {
+        let mut stats: Vec<PlayerSba> = self
+            .derived_state
+            .party
+            .values()
+            .map(|player| PlayerSba::new(player.index))
+            .collect();
+        let mut gauges: Vec<(u32, GaugeTracker)> = Vec::new();
+        // Players that took part in each chain.
+        let mut chains: Vec<Vec<u32>> = Vec::new();
+        // Player whose Skybound Art can still be continued into a chain.
+        let mut chain_starter: Option<u32> = None;
+        // Whether the last chain is still going, and who continued it without performing their Skybound Art yet.
+        let mut chain_open = false;
+        let mut continued_by: Vec<u32> = Vec::new();
+
+        for (timestamp, message) in self.encounter.event_log() {
+            let now = *timestamp;
+
+            let (actor_index, is_sba) = match message {
+                Message::OnUpdateSBA(event) => (event.actor_index, false),
+                Message::OnAttemptSBA(event) => (event.actor_index, false),
+                Message::OnPerformSBA(event) => (event.actor_index, true),
+                Message::OnContinueSBAChain(event) => (event.actor_index, true),
+                _ => continue,
+            };
+
+            let stat = match stats.iter().position(|stat| stat.index == actor_index) {
+                Some(position) => &mut stats[position],
+                None => {
+                    stats.push(PlayerSba::new(actor_index));
+                    stats.last_mut().unwrap()
+                }
+            };
+
+            let gauge = match gauges.iter().position(|(index, _)| *index == actor_index) {
+                Some(position) => &mut gauges[position].1,
+                None => {
+                    gauges.push((actor_index, GaugeTracker::default()));
+                    &mut gauges.last_mut().unwrap().1
+                }
+            };
+
+            let is_full =
+                matches!(message, Message::OnUpdateSBA(event) if event.sba_value >= FULL_GAUGE);
+
+            if is_full {
+                if let Some(empty_since) = gauge.empty_since.take() {
+                    stat.fill_times.push(now - empty_since);
+                }
+
+                gauge.full_since.get_or_insert(now);
+            } else if let Some(full_since) = gauge.full_since.take() {
+                stat.time_at_full += now - full_since;
+            }
+
+            if !is_sba {
+                continue;
+            }
+
+            match message {
+                Message::OnContinueSBAChain(_) => stat.chain_count += 1,
+                _ => stat.sba_count += 1,
+            }
+
+            gauge.empty_since = Some(now);
+
+            // A chain is only known once someone continues a Skybound Art, which joins it with the one before.
+            match message {
+                Message::OnContinueSBAChain(_) => {
+                    match chains.last_mut() {
+                        Some(chain) if chain_open => {
+                            if !chain.contains(&actor_index) {
+                                chain.push(actor_index);
+                            }
+                        }
+                        _ => {
+                            let mut chain: Vec<u32> = chain_starter.take().into_iter().collect();
+
+                            if !chain.contains(&actor_index) {
+                                chain.push(actor_index);
+                            }
+
+                            chains.push(chain);
+                        }
+                    }
+
+                    chain_open = true;
+                    continued_by.push(actor_index);
+                }
+                _ => {
+                    // The Skybound Art of a player that continued the chain is part of it, any other starts over.
+                    if let Some(position) =
+                        continued_by.iter().position(|index| *index == actor_index)
+                    {
+                        continued_by.remove(position);
+                    } else {
+                        chain_open = false;
+                        continued_by.clear();
+                        chain_starter = Some(actor_index);
+                    }
+                }
+            }
+        }
+
+        let end_time = self
+            .encounter
+            .event_log()
+            .last()
+            .map(|(timestamp, _)| *timestamp)
+            .unwrap_or_default();
+
+        for stat in stats.iter_mut() {
+            if let Some((_, gauge)) = gauges.iter().find(|(index, _)| *index == stat.index) {
+                if let Some(full_since) = gauge.full_since {
+                    stat.time_at_full += end_time - full_since;
+                }
+            }
+
+            if !stat.fill_times.is_empty() {
+                stat.average_fill_time =
+                    Some(stat.fill_times.iter().sum::<i64>() as f64 / stat.fill_times.len() as f64);
+            }
+
+            if !chains.is_empty() {
+                let participated = chains
+                    .iter()
+                    .filter(|chain| chain.contains(&stat.index))
+                    .count();
+
+                stat.chain_participation = participated as f64 / chains.len() as f64 * 100.0;
+            }
+
+            if let Some(player) = self.derived_state.party.get(&stat.index) {
+                stat.sba_damage = player
+                    .skill_breakdown
+                    .iter()
+                    .filter(|skill| matches!(skill.action_type, ActionType::SBA))
+                    .map(|skill| skill.total_damage)
+                    .sum();
+            }
+        }
+
+        stats.sort_by_key(|stat| stat.index);
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use protocol::{OnContinueSBAChainEvent, OnPerformSBAEvent, OnUpdateSBAEvent};
+
+    use super::*;
+
+    fn update(actor_index: u32, sba_value: f32) -> Message {
+        Message::OnUpdateSBA(OnUpdateSBAEvent {
+            actor_index,
+            sba_value,
+            sba_added: 0.0,
+        })
+    }
+
+    fn perform(actor_index: u32) -> Message {
+        Message::OnPerformSBA(OnPerformSBAEvent { actor_index })
+    }
+
+    #[test]
+    fn tracks_fill_times_wasted_gauge_and_chains() {
+        let mut parser = Parser::default();
+
+        parser.encounter.raw_event_log = vec![
+            (0, update(0, 1000.0)),
+            (0, update(1, 500.0)),
+            (2_000, perform(0)),
+            (
+                3_000,
+                Message::OnContinueSBAChain(OnContinueSBAChainEvent { actor_index: 1 }),
+            ),
+            (3_500, perform(1)),
+            (4_000, update(0, 500.0)),
+            (10_000, update(0, 1000.0)),
+            // Using a Skybound Art without anyone continuing it isn't a chain.
+            (13_000, perform(0)),
+            (20_000, update(0, 1000.0)),
+        ];
+
+        let stats = parser.sba_stats();
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].sba_count, 2);
+        assert_eq!(stats[0].fill_times, vec![8_000, 7_000]);
+        assert_eq!(stats[0].average_fill_time, Some(7_500.0));
+        // Full from 0 to 2s and 10s to 13s, and again at the end of the encounter.
+        assert_eq!(stats[0].time_at_full, 5_000);
+        assert_eq!(stats[0].chain_participation, 100.0);
+        assert_eq!(stats[1].chain_count, 1);
+        assert_eq!(stats[1].sba_count, 1);
+        assert_eq!(stats[1].chain_participation, 100.0);
+    }
+}
@@ -0,0 +1,140 @@
+use protocol::StatusChangeEvent;
+use serde::{Deserialize, Serialize};
+
+/// A single period of time a status was active.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusWindow {
+    pub start_time: i64,
+    /// None while the status is still active.
+    pub end_time: Option<i64>,
+    /// Index of the party member that applied the status.
+    pub applier_index: Option<u32>,
+}
+
+/// Derived uptime of a buff or debuff on an actor.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusState {
+    pub status_id: u32,
+    /// Last known skill that applied the status.
+    pub skill_id: Option<u32>,
+    /// Number of times the status was applied, including refreshes.
+    pub applications: u32,
+    /// Time the status was active in milliseconds, only counting windows that have ended.
+    pub total_time: i64,
+    /// Time the status was active, by the party member that applied it.
+    pub time_by_applier: Vec<(u32, i64)>,
+    pub windows: Vec<StatusWindow>,
+}
+
+impl StatusState {
+    pub fn new(status_id: u32) -> Self {
+        Self {
+            status_id,
+            skill_id: None,
+            applications: 0,
+            total_time: 0,
+            time_by_applier: Vec::new(),
+            windows: Vec::new(),
+        }
+    }
+
+    pub fn update_from_event(&mut self, now: i64, event: &StatusChangeEvent) {
+        if !event.active {
+            self.end(now);
+            return;
+        }
+
+        let applier_index = event.source.as_ref().map(|actor| actor.parent_index);
+
+        self.applications += 1;
+        self.skill_id = event.skill_id.or(self.skill_id);
+
+        // Refreshing the status from the same applier keeps the window going.
+        if let Some(window) = self.windows.last() {
+            if window.end_time.is_none() && window.applier_index == applier_index {
+                return;
+            }
+        }
+
+        self.end(now);
+        self.windows.push(StatusWindow {
+            start_time: now,
+            end_time: None,
+            applier_index,
+        });
+    }
+
+    /// Ends the active window, if any.
+    pub fn end(&mut self, now: i64) {
+        let Some(window) = self.windows.last_mut() else {
+            return;
+        };
+
+        if window.end_time.is_some() {
+            return;
+        }
+
+        let duration = now - window.start_time;
+
+        window.end_time = Some(now);
+        self.total_time += duration;
+
+        if let Some(applier_index) = window.applier_index {
+            match self
+                .time_by_applier
+                .iter_mut()
+                .find(|(index, _)| *index == applier_index)
+            {
+                Some((_, time)) => *time += duration,
+                None => self.time_by_applier.push((applier_index, duration)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use protocol::Actor;
+
+    use super::*;
+
+    fn status_event(applier: Option<u32>, active: bool) -> StatusChangeEvent {
+        StatusChangeEvent {
+            source: applier.map(|index| Actor {
+                index,
+                actor_type: 0,
+                parent_index: index,
+                parent_actor_type: 0,
+            }),
+            target: Actor {
+                index: 10,
+                actor_type: 0,
+                parent_index: 10,
+                parent_actor_type: 0,
+            },
+            status_id: 1,
+            skill_id: Some(1501),
+            active,
+        }
+    }
+
+    #[test]
+    fn uptime_is_attributed_to_the_applier() {
+        let mut status = StatusState::new(1);
+
+        status.update_from_event(0, &status_event(Some(0), true));
+        // Refreshing from the same player doesn't start a new window.
+        status.update_from_event(1000, &status_event(Some(0), true));
+        status.update_from_event(3000, &status_event(Some(1), true));
+        status.update_from_event(4000, &status_event(None, false));
+        status.update_from_event(5000, &status_event(None, false));
+
+        assert_eq!(status.applications, 3);
+        assert_eq!(status.total_time, 4000);
+        assert_eq!(status.time_by_applier, vec![(0, 3000), (1, 1000)]);
+        assert_eq!(status.windows.len(), 2);
+        assert_eq!(status.skill_id, Some(1501));
+    }
+}
@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+
+/// A stretch of time in which a party member was transformed.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransformationWindow {
+    /// Time the transformation was loaded in, or of the first transformed hit if that was missed.
+    pub start_time: i64,
+    /// Time of the next hit in the normal form, or the end of the encounter if it never came.
+    pub end_time: i64,
+    pub damage: u64,
+}
+
+/// Derived transformation uptime for a party member that can transform (e.g. Id's dragon form).
+///
+/// A window starts when the transformation is loaded in as its own player, and ends when the player hits in their
+/// normal form again. Transformations that started before the encounter was recorded start at their first hit.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransformationState {
+    pub index: u32,
+    pub windows: Vec<TransformationWindow>,
+    /// Time spent transformed, in milliseconds.
+    pub total_time: i64,
+    /// Percentage of the encounter spent transformed.
+    pub uptime: f64,
+    pub normal_damage: u64,
+    pub transformed_damage: u64,
+    /// Whether the player is currently transformed.
+    #[serde(skip)]
+    is_transformed: bool,
+}
+
+impl TransformationState {
+    pub fn new(index: u32) -> Self {
+        Self {
+            index,
+            windows: Vec::new(),
+            total_time: 0,
+            uptime: 0.0,
+            normal_damage: 0,
+            transformed_damage: 0,
+            is_transformed: false,
+        }
+    }
+
+    /// Starts a window, unless the player is already transformed.
+    pub fn start(&mut self, now: i64) {
+        if self.is_transformed {
+            return;
+        }
+
+        self.windows.push(TransformationWindow {
+            start_time: now,
+            end_time: now,
+            damage: 0,
+        });
+
+        self.is_transformed = true;
+    }
+
+    pub fn add_damage(&mut self, now: i64, damage: u64, is_transformed: bool) {
+        if !is_transformed {
+            self.normal_damage += damage;
+            self.end(now);
+            return;
+        }
+
+        self.start(now);
+        self.transformed_damage += damage;
+
+        if let Some(window) = self.windows.last_mut() {
+            window.damage += damage;
+        }
+    }
+
+    fn end(&mut self, now: i64) {
+        if !std::mem::take(&mut self.is_transformed) {
+            return;
+        }
+
+        if let Some(window) = self.windows.last_mut() {
+            window.end_time = now;
+        }
+    }
+
+    /// Extends the current window up to now and updates the uptime.
+    pub fn update(&mut self, now: i64, duration: i64) {
+        if self.is_transformed {
+            if let Some(window) = self.windows.last_mut() {
+                window.end_time = now;
+            }
+        }
+
+        self.total_time = self
+            .windows
+            .iter()
+            .map(|window| window.end_time - window.start_time)
+            .sum();
+        self.uptime = self.total_time as f64 / duration.max(1) as f64 * 100.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_last_from_the_transformation_until_the_next_normal_hit() {
+        let mut state = TransformationState::new(0);
+
+        state.add_damage(0, 100, false);
+        state.start(500);
+        state.add_damage(1_000, 200, true);
+        state.add_damage(3_000, 200, true);
+        state.add_damage(4_000, 100, false);
+        state.update(4_000, 4_000);
+
+        // The transformation load was missed, so this window starts at its first hit.
+        state.add_damage(6_000, 300, true);
+        state.update(10_000, 10_000);
+
+        assert_eq!(state.normal_damage, 200);
+        assert_eq!(state.transformed_damage, 700);
+        assert_eq!(
+            state.windows,
+            vec![
+                TransformationWindow {
+                    start_time: 500,
+                    end_time: 4_000,
+                    damage: 400,
+                },
+                TransformationWindow {
+                    start_time: 6_000,
+                    end_time: 10_000,
+                    damage: 300,
+                },
+            ]
+        );
+        assert_eq!(state.total_time, 7_500);
+        assert_eq!(state.uptime, 75.0);
+    }
+}
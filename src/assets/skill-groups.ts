@@ -1,7 +1,7 @@
 import { readTextFile } from "@tauri-apps/api/fs";
 import { resolveResource } from "@tauri-apps/api/path";
 
-type SkillGroupMapping = {
+export type SkillGroupMapping = {
   [key: string]: {
     [key: string]: {
       skills: number[];
@@ -68,6 +68,8 @@ export const Table = ({
         MeterColumns.DPS,
         MeterColumns.TotalStunValue,
         MeterColumns.StunPerSecond,
+        MeterColumns.DPSPerAttack,
+        MeterColumns.StunPerStunPower,
         MeterColumns.DamagePercentage,
       ];
 
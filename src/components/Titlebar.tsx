@@ -1,5 +1,5 @@
 import { ActionIcon, Menu, Tooltip } from "@mantine/core";
-import { Camera, ClipboardText, Minus, PushPinSimple } from "@phosphor-icons/react";
+import { Camera, ClipboardText, Minus, PushPinSimple, Warning } from "@phosphor-icons/react";
 import { invoke } from "@tauri-apps/api";
 import { appWindow } from "@tauri-apps/api/window";
 import { Fragment, useCallback } from "react";
@@ -99,6 +99,13 @@ export const Titlebar = ({
         {encounterState.totalDamage > 0 && <TeamDamageStats encounterState={encounterState} />}
       </div>
       <div data-tauri-drag-region className="titlebar-right">
+        {encounterState.missingEvents > 0 && (
+          <Tooltip label={t("ui.incomplete-data", { count: encounterState.missingEvents })} color="dark">
+            <div data-tauri-drag-region className="encounter-incomplete item">
+              <Warning size={16} />
+            </div>
+          </Tooltip>
+        )}
         <EncounterStatus encounterState={encounterState} elapsedTime={elapsedTime} />
         <Menu shadow="md" trigger="hover" openDelay={100} closeDelay={400}>
           <Menu.Target>
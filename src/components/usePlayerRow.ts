@@ -51,6 +51,10 @@ export const usePlayerRow = (live: boolean, player: ComputedPlayerState, partyDa
           : { value: (player.sba / 10).toFixed(2), unit: "%" };
       case MeterColumns.StunPerSecond:
         return { value: (player.stunPerSecond || 0).toLocaleString() };
+      case MeterColumns.DPSPerAttack:
+        return { value: (player.normalized?.dpsPer1kAttack || 0).toFixed(0) };
+      case MeterColumns.StunPerStunPower:
+        return { value: ((player.normalized?.stunPerStunPower || 0) * 10).toFixed(2) };
       case MeterColumns.TotalStunValue:
         return showFullValues
           ? { value: (player.totalStunValue || 0).toLocaleString() }
@@ -68,6 +72,8 @@ export const usePlayerRow = (live: boolean, player: ComputedPlayerState, partyDa
         MeterColumns.DPS,
         MeterColumns.TotalStunValue,
         MeterColumns.StunPerSecond,
+        MeterColumns.DPSPerAttack,
+        MeterColumns.StunPerStunPower,
         MeterColumns.DamagePercentage,
       ];
 
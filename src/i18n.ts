@@ -3,6 +3,7 @@ import LanguageDetector from "i18next-browser-languagedetector";
 import resourcesToBackend from "i18next-resources-to-backend";
 import { initReactI18next } from "react-i18next";
 
+import { invoke } from "@tauri-apps/api";
 import { readTextFile } from "@tauri-apps/api/fs";
 import { resolveResource } from "@tauri-apps/api/path";
 
@@ -59,4 +60,9 @@ declare global {
 
 window.i18n = i18n;
 
+// Exports are written by the backend, so it needs to know which language to use.
+i18n.on("languageChanged", (language) => {
+  invoke("set_locale", { locale: language });
+});
+
 export default i18n;
@@ -162,7 +162,7 @@ export const IndexPage = () => {
                   </SortableColumn>
                 </Table.Th>
                 <Table.Th>{t("ui.logs.quest-name")}</Table.Th>
-                <Table.Th></Table.Th>
+                <Table.Th>{t("ui.logs.quest-status")}</Table.Th>
                 <Table.Th>{t("ui.logs.primary-target")}</Table.Th>
                 <Table.Th>
                   <SortableColumn
@@ -242,6 +242,8 @@ function LogEntry({
   names: string;
   resetSelectedTargets: () => void;
 }): JSX.Element {
+  const { t } = useTranslation();
+
   return (
     <Table.Tr key={log.id}>
       <Table.Td>
@@ -261,7 +263,9 @@ function LogEntry({
       <Table.Td>
         <Text size="xs">{translateQuestId(log.questId)}</Text>
       </Table.Td>
-      <Table.Td>{log.questId && log.questCompleted !== null && (log.questCompleted ? "✓" : "X")}</Table.Td>
+      <Table.Td>
+        {log.questId && <Text size="xs">{t(`ui.logs.quest-outcomes.${log.questOutcome ?? "Unknown"}`)}</Text>}
+      </Table.Td>
       <Table.Td>
         <Text size="xs">{primaryTarget}</Text>
       </Table.Td>
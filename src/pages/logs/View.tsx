@@ -3,6 +3,7 @@ import {
   ActionIcon,
   Box,
   Button,
+  Checkbox,
   Divider,
   Flex,
   Group,
@@ -23,17 +24,25 @@ import { useCallback, useEffect, useState } from "react";
 import toast from "react-hot-toast";
 import { Link, useParams } from "react-router-dom";
 
+import SkillGroupMapping from "@/assets/skill-groups";
 import { Table as MeterTable } from "@/components/Table";
 import { EncounterStateResponse, useEncounterStore } from "@/stores/useEncounterStore";
 import { useMeterSettingsStore } from "@/stores/useMeterSettingsStore";
 import {
   MeterColumns,
   type ComputedPlayerState,
+  type EnemyCatalogEntry,
+  type EnemyCategory,
   type EnemyType,
+  type LoadoutHistoryEntry,
   type Overmastery,
   type PlayerData,
+  type PlayerRotation,
+  type PlayerStats,
   type SortDirection,
   type SortType,
+  type StatusGroup,
+  type StatusState,
 } from "@/types";
 import {
   EMPTY_ID,
@@ -44,6 +53,7 @@ import {
   exportScreenshotToClipboard,
   exportSimpleEncounterToClipboard,
   formatInPartyOrder,
+  groupStatuses,
   humanizeNumbers,
   millisecondsToElapsedFormat,
   openDamageCalculator,
@@ -155,6 +165,29 @@ export const ChartTooltip = ({ label, payload }: ChartTooltipProps) => {
 
 const DPS_INTERVAL = 3;
 
+const formatSeconds = (ms: number | null): string => (ms === null ? "---" : `${(ms / 1000).toFixed(1)}s`);
+
+const formatDps = (dps: number | null): string => (dps === null ? "---" : Math.round(dps).toLocaleString());
+
+const STAT_LABELS: Array<[keyof PlayerStats, string]> = [
+  ["level", "ui.stats.level"],
+  ["totalHp", "ui.stats.total-hp"],
+  ["totalAttack", "ui.stats.total-attack"],
+  ["criticalRate", "ui.stats.critical-rate"],
+  ["stunPower", "ui.stats.stun-power"],
+  ["totalPower", "ui.stats.total-power"],
+];
+
+/// Lists the stats that differ between two sets of stats, e.g. "Total Attack: 9000 → 9500".
+const formatStatChanges = (before: PlayerStats | null, after: PlayerStats | null): string => {
+  const format = (value: number | undefined) =>
+    value === undefined ? "---" : Number.isInteger(value) ? value : value.toFixed(1);
+
+  return STAT_LABELS.filter(([key]) => before?.[key] !== after?.[key])
+    .map(([key, label]) => `${t(label)}: ${format(before?.[key])} → ${format(after?.[key])}`)
+    .join(", ");
+};
+
 export const ViewPage = () => {
   const { color_1, color_2, color_3, color_4, show_display_names, streamer_mode } = useMeterSettingsStore(
     useShallow((state) => ({
@@ -176,13 +209,16 @@ export const ViewPage = () => {
     dpsChart,
     sbaChart,
     sbaEvents,
+    sbaStats,
+    linkAttacks,
     chartLen,
     sbaChartLen,
     targets,
     selectedTargets,
+    statChanges,
     questId,
     questTimer,
-    questCompleted,
+    questOutcome,
     playerData,
     setSelectedTargets,
     loadFromResponse,
@@ -191,29 +227,76 @@ export const ViewPage = () => {
     dpsChart: state.dpsChart,
     sbaChart: state.sbaChart,
     sbaEvents: state.sbaEvents,
+    sbaStats: state.sbaStats,
+    linkAttacks: state.linkAttacks,
     chartLen: state.chartLen,
     sbaChartLen: state.sbaChartLen,
     targets: state.targets,
     selectedTargets: state.selectedTargets,
+    statChanges: state.statChanges,
     playerData: state.players,
     questId: state.questId,
     questTimer: state.questTimer,
-    questCompleted: state.questCompleted,
+    questOutcome: state.questOutcome,
     setSelectedTargets: state.setSelectedTargets,
     loadFromResponse: state.loadFromResponse,
   }));
   const [sortType, setSortType] = useState<SortType>(MeterColumns.TotalDamage);
   const [sortDirection, setSortDirection] = useState<SortDirection>("desc");
+  const [bossesOnly, setBossesOnly] = useState(false);
+  const [rotation, setRotation] = useState<PlayerRotation[]>([]);
+  const [enemyCatalog, setEnemyCatalog] = useState<EnemyCatalogEntry[]>([]);
+  /** Loadout history of each player, keyed by their actor index */
+  const [loadoutHistory, setLoadoutHistory] = useState<Record<number, LoadoutHistoryEntry[]>>({});
 
   useEffect(() => {
-    invoke("fetch_encounter_state", { id: Number(id), options: { targets: selectedTargets } })
+    invoke("fetch_encounter_state", { id: Number(id), options: { targets: selectedTargets, bossesOnly } })
       .then((result) => {
         loadFromResponse(result as EncounterStateResponse);
       })
       .catch((e) => {
         toast.error(`Failed to fetch encounter state: ${e}`);
       });
-  }, [id, selectedTargets]);
+  }, [id, selectedTargets, bossesOnly]);
+
+  useEffect(() => {
+    invoke("fetch_enemy_catalog")
+      .then((result) => {
+        setEnemyCatalog(result as EnemyCatalogEntry[]);
+      })
+      .catch((e) => {
+        toast.error(`Failed to fetch enemy catalog: ${e}`);
+      });
+  }, []);
+
+  useEffect(() => {
+    invoke("fetch_rotation", { id: Number(id) })
+      .then((result) => {
+        setRotation(result as PlayerRotation[]);
+      })
+      .catch((e) => {
+        toast.error(`Failed to fetch rotation: ${e}`);
+      });
+  }, [id]);
+
+  useEffect(() => {
+    Promise.all(
+      playerData
+        .filter((player) => typeof player.characterType == "string")
+        .map((player) =>
+          invoke("fetch_loadout_history", {
+            playerId: player.displayName,
+            playerCharacter: player.characterType,
+          }).then((result): [number, LoadoutHistoryEntry[]] => [player.actorIndex, result as LoadoutHistoryEntry[]])
+        )
+    )
+      .then((entries) => {
+        setLoadoutHistory(Object.fromEntries(entries));
+      })
+      .catch((e) => {
+        toast.error(`Failed to fetch loadout history: ${e}`);
+      });
+  }, [playerData]);
 
   const handleCharacterDataCopy = useCallback((player: PlayerData) => {
     if (player) exportCharacterDataToClipboard(player);
@@ -236,8 +319,12 @@ export const ViewPage = () => {
   }, []);
 
   const exportDamageLogToFile = useCallback(() => {
-    if (id) invoke("export_damage_log_to_file", { id: Number(id), options: { targets: selectedTargets } });
-  }, [id, selectedTargets]);
+    if (id) invoke("export_damage_log_to_file", { id: Number(id), options: { targets: selectedTargets, bossesOnly } });
+  }, [id, selectedTargets, bossesOnly]);
+
+  const exportTraitTotalsToFile = useCallback(() => {
+    if (id) invoke("export_trait_totals_to_file", { id: Number(id) });
+  }, [id]);
 
   if (!encounter) {
     return (
@@ -256,6 +343,18 @@ export const ViewPage = () => {
 
   const players = formatInPartyOrder(encounter.party);
 
+  const playerName = (index: number) => {
+    const player = players.find((p) => p.index === index);
+    const partySlotIndex = playerData.findIndex((partyMember) => partyMember?.actorIndex === index);
+
+    return translatedPlayerName(
+      partySlotIndex,
+      playerData[partySlotIndex],
+      player as ComputedPlayerState,
+      show_display_names && !streamer_mode
+    );
+  };
+
   for (let i = 0; i < chartLen + 1; i++) {
     const datapoint: {
       timestamp?: string;
@@ -343,23 +442,77 @@ export const ViewPage = () => {
     strokeDasharray: "2 2",
   });
 
-  const targetItems = targets.map((target) => {
+  const enemyName = (target: EnemyType) => {
     if (typeof target == "object" && Object.hasOwn(target, "Unknown")) {
       const hash = target.Unknown.toString(16).padStart(8, "0");
 
-      return {
-        rawValue: target,
-        value: target.Unknown.toString(),
-        label: t([`enemies:${hash}.text`, `enemies.unknown.${hash}`, "enemies.unknown-type"], { id: hash }),
-      };
+      return t([`enemies:${hash}.text`, `enemies.unknown.${hash}`, "enemies.unknown-type"], { id: hash });
     }
 
-    return {
-      rawValue: target,
-      value: target.toString(),
-      label: t([`enemies.${target}`, "enemies.unknown-type"]),
-    };
-  });
+    return t([`enemies.${target}`, "enemies.unknown-type"]);
+  };
+
+  const targetItems = targets.map((target) => ({
+    rawValue: target,
+    value: typeof target == "object" ? target.Unknown.toString() : target.toString(),
+    label: enemyName(target),
+  }));
+
+  const targetStates = Object.values(encounter.targets).sort((a, b) => b.totalDamage - a.totalDamage);
+
+  const characterTypeOf = (index: number) => players.find((player) => player.index === index)?.characterType;
+  const statusGroups = (statuses: StatusState[]) =>
+    groupStatuses(statuses, characterTypeOf, SkillGroupMapping, encounter.endTime);
+
+  const buffs = Object.entries(encounter.buffs).flatMap(([index, statuses]) =>
+    statusGroups(statuses).map((group) => ({ index: Number(index), group }))
+  );
+  const debuffs = targetStates.flatMap((target) =>
+    statusGroups(target.debuffs).map((group) => ({ target, group }))
+  );
+
+  const statusName = (group: StatusGroup) => {
+    const characterType = group.characterType ?? "default";
+    const id = toHashString(group.statusIds[0]);
+
+    if (group.skillGroup !== null) {
+      return t([
+        `skills.${characterType}.skill-groups.${group.skillGroup}`,
+        `skills.default.skill-groups.${group.skillGroup}`,
+      ]);
+    }
+
+    if (group.skillId !== null) {
+      const keys = [`skills.${characterType}.${group.skillId}`, `skills.default.${group.skillId}`];
+
+      return t([...keys, "ui.logs.unknown-status"], { id });
+    }
+
+    return t("ui.logs.unknown-status", { id });
+  };
+
+  const formatUptime = (group: StatusGroup) => {
+    const duration = encounter.endTime - encounter.startTime;
+
+    return `${((group.totalTime / Math.max(duration, 1)) * 100).toFixed(1)}%`;
+  };
+
+  const formatAppliers = (group: StatusGroup) =>
+    group.timeByApplier.map(([index, time]) => `${playerName(index)} (${formatSeconds(time)})`).join(", ") || "---";
+
+  // Groups the targets by their category in the catalog, targets missing from it go last.
+  const targetCategory = (target: EnemyType): EnemyCategory | "Unknown" => {
+    const hash = typeof target == "object" ? target.Unknown : undefined;
+
+    return enemyCatalog.find((enemy) => enemy.hash === hash)?.category ?? "Unknown";
+  };
+
+  const targetGroups = (["Boss", "Add", "Object", "Unknown"] as const)
+    .map((category) => ({
+      group: t(`ui.logs.enemy-categories.${category}`),
+      items: targetItems.filter((item) => targetCategory(item.rawValue) === category),
+    }))
+    .filter((group) => group.items.length > 0);
 
   return (
     <Box>
@@ -382,6 +535,7 @@ export const ViewPage = () => {
                 <Menu.Item onClick={handleFullEncounterCopy}>{t("ui.copy-to-clipboard-full")}</Menu.Item>
                 <Menu.Item onClick={handleScreenshotCopy}>{t("ui.copy-screenshot-to-clipboard")}</Menu.Item>
                 <Menu.Item onClick={exportDamageLogToFile}>{t("ui.export-damage-log")}</Menu.Item>
+                <Menu.Item onClick={exportTraitTotalsToFile}>{t("ui.export-trait-totals")}</Menu.Item>
               </Menu.Dropdown>
             </Menu>
           </Flex>
@@ -408,7 +562,7 @@ export const ViewPage = () => {
                 {t("ui.logs.quest-status")}:
               </Text>
               <Text size="sm" fs="italic" ml={4}>
-                {questCompleted ? "✅" : "❌"}
+                {t(`ui.logs.quest-outcomes.${questOutcome}`)}
               </Text>
             </Box>
           )}
@@ -454,15 +608,33 @@ export const ViewPage = () => {
           <Tabs.List>
             <Tabs.Tab value="overview">{t("ui.logs.overview")}</Tabs.Tab>
             <Tabs.Tab value="sba">{t("ui.logs.sba-chart")}</Tabs.Tab>
+            <Tabs.Tab value="link-attacks" disabled={linkAttacks.links.length === 0}>
+              {t("ui.logs.link-attacks")}
+            </Tabs.Tab>
+            <Tabs.Tab value="rotation" disabled={rotation.length === 0}>
+              {t("ui.logs.rotation")}
+            </Tabs.Tab>
             <Tabs.Tab value="equipment" disabled={playerData.length === 0}>
               {t("ui.logs.equipment")}
             </Tabs.Tab>
+            <Tabs.Tab value="loadouts" disabled={playerData.length === 0}>
+              {t("ui.logs.loadouts")}
+            </Tabs.Tab>
+            <Tabs.Tab value="stat-changes" disabled={statChanges.length === 0}>
+              {t("ui.logs.stat-changes")}
+            </Tabs.Tab>
+            <Tabs.Tab value="targets" disabled={targetStates.length === 0}>
+              {t("ui.logs.targets")}
+            </Tabs.Tab>
+            <Tabs.Tab value="statuses" disabled={buffs.length === 0 && debuffs.length === 0}>
+              {t("ui.logs.statuses")}
+            </Tabs.Tab>
           </Tabs.List>
           <Tabs.Panel value="overview">
             <Box mt="md">
               <Stack>
                 <MultiSelect
-                  data={targetItems}
+                  data={targetGroups}
                   placeholder="All"
                   clearable
                   onChange={(value) => {
@@ -473,6 +645,12 @@ export const ViewPage = () => {
                     setSelectedTargets(targets);
                   }}
                 />
+                <Checkbox
+                  label={t("ui.logs.bosses-only")}
+                  checked={bossesOnly}
+                  disabled={selectedTargets.length > 0}
+                  onChange={(event) => setBossesOnly(event.currentTarget.checked)}
+                />
                 <MeterTable
                   encounterState={encounter}
                   sortType={sortType}
@@ -517,6 +695,46 @@ export const ViewPage = () => {
                   content: ({ label, payload }) => <ChartTooltip label={label} payload={payload} />,
                 }}
               />
+              <Table striped layout="fixed">
+                <Table.Thead>
+                  <Table.Tr>
+                    <Table.Th>{t("ui.logs.sba-columns.player")}</Table.Th>
+                    <Table.Th>{t("ui.logs.sba-columns.sba-count")}</Table.Th>
+                    <Table.Th>{t("ui.logs.sba-columns.chain-count")}</Table.Th>
+                    <Table.Th>{t("ui.logs.sba-columns.average-fill-time")}</Table.Th>
+                    <Table.Th>{t("ui.logs.sba-columns.time-at-full")}</Table.Th>
+                    <Table.Th>{t("ui.logs.sba-columns.chain-participation")}</Table.Th>
+                    <Table.Th>{t("ui.logs.sba-columns.sba-damage")}</Table.Th>
+                  </Table.Tr>
+                </Table.Thead>
+                <Table.Tbody>
+                  {sbaStats.map((stat) => (
+                    <Table.Tr key={stat.index}>
+                      <Table.Td>
+                        <Text size="xs">{playerName(stat.index)}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{stat.sbaCount}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{stat.chainCount}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{formatSeconds(stat.averageFillTime)}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{formatSeconds(stat.timeAtFull)}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{stat.chainParticipation.toFixed(0)}%</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{stat.sbaDamage.toLocaleString()}</Text>
+                      </Table.Td>
+                    </Table.Tr>
+                  ))}
+                </Table.Tbody>
+              </Table>
               <Table striped layout="fixed">
                 <Table.Tbody>
                   {sbaEvents.map((payload, index) => {
@@ -555,6 +773,130 @@ export const ViewPage = () => {
               </Table>
             </Group>
           </Tabs.Panel>
+          <Tabs.Panel value="link-attacks">
+            <Stack mt="20" gap="xs">
+              <Table striped layout="fixed">
+                <Table.Thead>
+                  <Table.Tr>
+                    <Table.Th>{t("ui.logs.link-attack-columns.player")}</Table.Th>
+                    <Table.Th>{t("ui.logs.link-attack-columns.first-hits")}</Table.Th>
+                    <Table.Th>{t("ui.logs.link-attack-columns.participated")}</Table.Th>
+                    <Table.Th>{t("ui.logs.link-attack-columns.participation")}</Table.Th>
+                    <Table.Th>{t("ui.logs.link-attack-columns.total-damage")}</Table.Th>
+                    <Table.Th>{t("ui.logs.link-attack-columns.average-damage")}</Table.Th>
+                  </Table.Tr>
+                </Table.Thead>
+                <Table.Tbody>
+                  {linkAttacks.players.map((player) => (
+                    <Table.Tr key={player.index}>
+                      <Table.Td>
+                        <Text size="xs">{playerName(player.index)}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{player.firstHits}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{player.participated}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{player.participation.toFixed(0)}%</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{player.totalDamage.toLocaleString()}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{Math.round(player.averageDamage).toLocaleString()}</Text>
+                      </Table.Td>
+                    </Table.Tr>
+                  ))}
+                </Table.Tbody>
+              </Table>
+              <Table striped layout="fixed">
+                <Table.Thead>
+                  <Table.Tr>
+                    <Table.Th>{t("ui.logs.link-attack-columns.time")}</Table.Th>
+                    <Table.Th>{t("ui.logs.link-attack-columns.first-hit-by")}</Table.Th>
+                    <Table.Th>{t("ui.logs.link-attack-columns.participants")}</Table.Th>
+                    <Table.Th>{t("ui.logs.link-attack-columns.total-damage")}</Table.Th>
+                  </Table.Tr>
+                </Table.Thead>
+                <Table.Tbody>
+                  {linkAttacks.links.map((link) => (
+                    <Table.Tr key={link.startTime}>
+                      <Table.Td>
+                        <Text size="xs">{millisecondsToElapsedFormat(link.startTime)}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{playerName(link.firstHitBy)}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">
+                          {link.participants
+                            .map(({ index, damage }) => `${playerName(index)} (${damage.toLocaleString()})`)
+                            .join(", ")}
+                        </Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{link.totalDamage.toLocaleString()}</Text>
+                      </Table.Td>
+                    </Table.Tr>
+                  ))}
+                </Table.Tbody>
+              </Table>
+            </Stack>
+          </Tabs.Panel>
+          <Tabs.Panel value="rotation">
+            <Stack mt="20" gap="xs">
+              {rotation.map((playerRotation) => (
+                <Box key={playerRotation.index}>
+                  <Text size="sm" fw={700}>
+                    {playerName(playerRotation.index)}
+                  </Text>
+                  <Table striped layout="fixed">
+                    <Table.Thead>
+                      <Table.Tr>
+                        <Table.Th>{t("ui.logs.rotation-columns.skill")}</Table.Th>
+                        <Table.Th>{t("ui.logs.rotation-columns.casts")}</Table.Th>
+                        <Table.Th>{t("ui.logs.rotation-columns.average-interval")}</Table.Th>
+                        <Table.Th>{t("ui.logs.rotation-columns.shortest-interval")}</Table.Th>
+                        <Table.Th>{t("ui.logs.rotation-columns.average-delay")}</Table.Th>
+                      </Table.Tr>
+                    </Table.Thead>
+                    <Table.Tbody>
+                      {playerRotation.skills.map((skill) => (
+                        <Table.Tr key={skill.skillId}>
+                          <Table.Td>
+                            <Text size="xs">
+                              {t(
+                                [
+                                  `skills.${playerRotation.characterType}.${skill.skillId}`,
+                                  `skills.default.${skill.skillId}`,
+                                  "skills.default.unknown-skill",
+                                ],
+                                { id: skill.skillId }
+                              )}
+                            </Text>
+                          </Table.Td>
+                          <Table.Td>
+                            <Text size="xs">{skill.casts}</Text>
+                          </Table.Td>
+                          <Table.Td>
+                            <Text size="xs">{formatSeconds(skill.averageInterval)}</Text>
+                          </Table.Td>
+                          <Table.Td>
+                            <Text size="xs">{formatSeconds(skill.shortestInterval)}</Text>
+                          </Table.Td>
+                          <Table.Td>
+                            <Text size="xs">{formatSeconds(skill.averageDelay)}</Text>
+                          </Table.Td>
+                        </Table.Tr>
+                      ))}
+                    </Table.Tbody>
+                  </Table>
+                </Box>
+              ))}
+            </Stack>
+          </Tabs.Panel>
           <Tabs.Panel value="equipment">
             <Group mt="20" gap="xs">
               <Table striped layout="fixed">
@@ -691,6 +1033,29 @@ export const ViewPage = () => {
                       );
                     })}
                   </Table.Tr>
+                  <Table.Tr>
+                    {playerData.map((player) => {
+                      return (
+                        <Table.Td key={player.actorIndex}>
+                          <Text size="xs" fw={700}>
+                            {t("ui.trait-totals")}
+                          </Text>
+                          {(player.traitTotals || []).map((total) => (
+                            <Text
+                              key={total.traitId}
+                              size="xs"
+                              fs="italic"
+                              fw={300}
+                              c={total.overflow !== null && total.overflow > 0 ? "red" : undefined}
+                              title={total.maxLevel === null ? t("ui.unknown-max-level") : undefined}
+                            >
+                              - {translateTraitId(total.traitId)} (Lvl. {total.level}/{total.maxLevel ?? "?"})
+                            </Text>
+                          ))}
+                        </Table.Td>
+                      );
+                    })}
+                  </Table.Tr>
                   {Array.from(Array(12).keys()).map((sigilIndex) => (
                     <Table.Tr key={sigilIndex}>
                       {playerData.map((player) => {
@@ -723,6 +1088,252 @@ export const ViewPage = () => {
               </Table>
             </Group>
           </Tabs.Panel>
+          <Tabs.Panel value="loadouts">
+            <Stack mt="20" gap="xs">
+              {playerData.map((player) => (
+                <Box key={player.actorIndex}>
+                  <Text size="sm" fw={700}>
+                    {formatPlayerDisplayName(player, show_display_names && !streamer_mode, false)}
+                  </Text>
+                  <Table striped layout="fixed">
+                    <Table.Thead>
+                      <Table.Tr>
+                        <Table.Th>{t("ui.logs.loadout-columns.first-seen")}</Table.Th>
+                        <Table.Th>{t("ui.logs.loadout-columns.last-seen")}</Table.Th>
+                        <Table.Th>{t("ui.logs.loadout-columns.logs")}</Table.Th>
+                        <Table.Th>{t("ui.logs.loadout-columns.best-dps")}</Table.Th>
+                        <Table.Th>{t("ui.logs.loadout-columns.average-dps")}</Table.Th>
+                      </Table.Tr>
+                    </Table.Thead>
+                    <Table.Tbody>
+                      {(loadoutHistory[player.actorIndex] || []).map((entry) => {
+                        // The loadout used in this log is highlighted.
+                        const fw = entry.logIds.includes(Number(id)) ? 700 : undefined;
+
+                        return (
+                          <Table.Tr key={entry.fingerprint}>
+                            <Table.Td>
+                              <Text size="xs" fw={fw}>
+                                {epochToLocalTime(entry.firstSeen)}
+                              </Text>
+                            </Table.Td>
+                            <Table.Td>
+                              <Text size="xs" fw={fw}>
+                                {epochToLocalTime(entry.lastSeen)}
+                              </Text>
+                            </Table.Td>
+                            <Table.Td>
+                              <Text size="xs" fw={fw}>
+                                {entry.logIds.length}
+                              </Text>
+                            </Table.Td>
+                            <Table.Td>
+                              <Text size="xs" fw={fw}>
+                                {formatDps(entry.bestDps)}
+                              </Text>
+                            </Table.Td>
+                            <Table.Td>
+                              <Text size="xs" fw={fw}>
+                                {formatDps(entry.averageDps)}
+                              </Text>
+                            </Table.Td>
+                          </Table.Tr>
+                        );
+                      })}
+                    </Table.Tbody>
+                  </Table>
+                </Box>
+              ))}
+            </Stack>
+          </Tabs.Panel>
+          <Tabs.Panel value="stat-changes">
+            <Group mt="20" gap="xs">
+              <Table striped layout="fixed">
+                <Table.Thead>
+                  <Table.Tr>
+                    <Table.Th>{t("ui.logs.stat-change-columns.time")}</Table.Th>
+                    <Table.Th>{t("ui.logs.stat-change-columns.player")}</Table.Th>
+                    <Table.Th>{t("ui.logs.stat-change-columns.stats")}</Table.Th>
+                    <Table.Th>{t("ui.logs.stat-change-columns.loadout-changed")}</Table.Th>
+                  </Table.Tr>
+                </Table.Thead>
+                <Table.Tbody>
+                  {statChanges.map((change, index) => (
+                    <Table.Tr key={index}>
+                      <Table.Td>
+                        <Text size="xs">{millisecondsToElapsedFormat(change.timestamp)}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{playerName(change.actorIndex)}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{formatStatChanges(change.before, change.after) || "---"}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{change.loadoutChanged ? "✓" : "---"}</Text>
+                      </Table.Td>
+                    </Table.Tr>
+                  ))}
+                </Table.Tbody>
+              </Table>
+            </Group>
+          </Tabs.Panel>
+          <Tabs.Panel value="targets">
+            <Stack mt="20" gap="xs">
+              <Table striped layout="fixed">
+                <Table.Thead>
+                  <Table.Tr>
+                    <Table.Th>{t("ui.logs.target-columns.target")}</Table.Th>
+                    <Table.Th>{t("ui.logs.target-columns.total-damage")}</Table.Th>
+                    <Table.Th>{t("ui.logs.target-columns.stuns")}</Table.Th>
+                    <Table.Th>{t("ui.logs.target-columns.stun-time")}</Table.Th>
+                    <Table.Th>{t("ui.logs.target-columns.stun-damage")}</Table.Th>
+                    <Table.Th>{t("ui.logs.target-columns.killed-at")}</Table.Th>
+                  </Table.Tr>
+                </Table.Thead>
+                <Table.Tbody>
+                  {targetStates.map((target) => {
+                    const stun = target.conditions.find((condition) => condition.condition === "Stun");
+
+                    return (
+                      <Table.Tr key={target.index}>
+                        <Table.Td>
+                          <Text size="xs">{enemyName(target.targetType)}</Text>
+                        </Table.Td>
+                        <Table.Td>
+                          <Text size="xs">{target.totalDamage.toLocaleString()}</Text>
+                        </Table.Td>
+                        <Table.Td>
+                          <Text size="xs">{stun?.count ?? 0}</Text>
+                        </Table.Td>
+                        <Table.Td>
+                          <Text size="xs">{formatSeconds(stun?.totalTime ?? null)}</Text>
+                        </Table.Td>
+                        <Table.Td>
+                          <Text size="xs">{(stun?.totalDamage ?? 0).toLocaleString()}</Text>
+                        </Table.Td>
+                        <Table.Td>
+                          <Text size="xs">
+                            {target.killedAt === null
+                              ? "---"
+                              : millisecondsToElapsedFormat(target.killedAt - encounter.startTime)}
+                          </Text>
+                        </Table.Td>
+                      </Table.Tr>
+                    );
+                  })}
+                </Table.Tbody>
+              </Table>
+              <Table striped layout="fixed">
+                <Table.Thead>
+                  <Table.Tr>
+                    <Table.Th>{t("ui.logs.target-columns.target")}</Table.Th>
+                    <Table.Th>{t("ui.logs.target-columns.stunned-at")}</Table.Th>
+                    <Table.Th>{t("ui.logs.target-columns.duration")}</Table.Th>
+                    <Table.Th>{t("ui.logs.target-columns.stunned-by")}</Table.Th>
+                    <Table.Th>{t("ui.logs.target-columns.stun-damage")}</Table.Th>
+                  </Table.Tr>
+                </Table.Thead>
+                <Table.Tbody>
+                  {targetStates.flatMap((target) =>
+                    target.conditions.flatMap((condition) =>
+                      condition.windows.map((window) => (
+                        <Table.Tr key={`${target.index}-${condition.condition}-${window.startTime}`}>
+                          <Table.Td>
+                            <Text size="xs">{enemyName(target.targetType)}</Text>
+                          </Table.Td>
+                          <Table.Td>
+                            <Text size="xs">{millisecondsToElapsedFormat(window.startTime - encounter.startTime)}</Text>
+                          </Table.Td>
+                          <Table.Td>
+                            <Text size="xs">
+                              {formatSeconds(window.endTime === null ? null : window.endTime - window.startTime)}
+                            </Text>
+                          </Table.Td>
+                          <Table.Td>
+                            <Text size="xs">
+                              {window.triggerIndex === null ? "---" : playerName(window.triggerIndex)}
+                            </Text>
+                          </Table.Td>
+                          <Table.Td>
+                            <Text size="xs">{window.damage.toLocaleString()}</Text>
+                          </Table.Td>
+                        </Table.Tr>
+                      ))
+                    )
+                  )}
+                </Table.Tbody>
+              </Table>
+            </Stack>
+          </Tabs.Panel>
+          <Tabs.Panel value="statuses">
+            <Stack mt="20" gap="xs">
+              <Table striped layout="fixed">
+                <Table.Thead>
+                  <Table.Tr>
+                    <Table.Th>{t("ui.logs.status-columns.player")}</Table.Th>
+                    <Table.Th>{t("ui.logs.status-columns.buff")}</Table.Th>
+                    <Table.Th>{t("ui.logs.status-columns.uptime")}</Table.Th>
+                    <Table.Th>{t("ui.logs.status-columns.applications")}</Table.Th>
+                    <Table.Th>{t("ui.logs.status-columns.applied-by")}</Table.Th>
+                  </Table.Tr>
+                </Table.Thead>
+                <Table.Tbody>
+                  {buffs.map(({ index, group }) => (
+                    <Table.Tr key={`${index}-${group.statusIds.join("-")}`}>
+                      <Table.Td>
+                        <Text size="xs">{playerName(index)}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{statusName(group)}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{formatUptime(group)}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{group.applications}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{formatAppliers(group)}</Text>
+                      </Table.Td>
+                    </Table.Tr>
+                  ))}
+                </Table.Tbody>
+              </Table>
+              <Table striped layout="fixed">
+                <Table.Thead>
+                  <Table.Tr>
+                    <Table.Th>{t("ui.logs.status-columns.target")}</Table.Th>
+                    <Table.Th>{t("ui.logs.status-columns.debuff")}</Table.Th>
+                    <Table.Th>{t("ui.logs.status-columns.uptime")}</Table.Th>
+                    <Table.Th>{t("ui.logs.status-columns.applications")}</Table.Th>
+                    <Table.Th>{t("ui.logs.status-columns.applied-by")}</Table.Th>
+                  </Table.Tr>
+                </Table.Thead>
+                <Table.Tbody>
+                  {debuffs.map(({ target, group }) => (
+                    <Table.Tr key={`${target.index}-${group.statusIds.join("-")}`}>
+                      <Table.Td>
+                        <Text size="xs">{enemyName(target.targetType)}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{statusName(group)}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{formatUptime(group)}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{group.applications}</Text>
+                      </Table.Td>
+                      <Table.Td>
+                        <Text size="xs">{formatAppliers(group)}</Text>
+                      </Table.Td>
+                    </Table.Tr>
+                  ))}
+                </Table.Tbody>
+              </Table>
+            </Stack>
+          </Tabs.Panel>
         </Tabs>
       </Box>
     </Box>
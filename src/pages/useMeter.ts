@@ -2,6 +2,7 @@ import { useMeterSettingsStore } from "@/stores/useMeterSettingsStore";
 import {
   EncounterState,
   EncounterUpdateEvent,
+  HookStatusEvent,
   MeterColumns,
   PartyUpdateEvent,
   PlayerData,
@@ -23,6 +24,9 @@ const DEFAULT_ENCOUNTER_STATE: EncounterState = {
   party: {},
   targets: {},
   status: "Waiting",
+  missingEvents: 0,
+  buffs: {},
+  transformations: {},
 };
 
 export default function useMeter() {
@@ -83,6 +87,15 @@ export default function useMeter() {
       setPartyData(event.payload);
     });
 
+    const onHookStatus = listen("hook-status", (event: HookStatusEvent) => {
+      // Unavailable hooks haven't been located yet, so only warn about the ones that failed to install.
+      const failedHooks = event.payload.hooks.filter((hook) => hook.state === "Failed").map((hook) => hook.name);
+
+      if (failedHooks.length > 0) {
+        toast.error(t("ui.hook-status-failed", { hooks: failedHooks.join(", ") }));
+      }
+    });
+
     const onSuccessAlert = listen("success-alert", (evt) => {
       toast.success(evt.payload as string);
     });
@@ -105,6 +118,7 @@ export default function useMeter() {
       encounterSavedErrorListener.then((f) => f());
       onAreaEnterListener.then((f) => f());
       onPartyUpdate.then((f) => f());
+      onHookStatus.then((f) => f());
       onSuccessAlert.then((f) => f());
       onErrorAlert.then((f) => f());
       onPinned.then((f) => f());
@@ -1,4 +1,16 @@
-import { CharacterType, DeathEvent, EncounterState, EnemyType, PlayerData, SBAEvent } from "@/types";
+import {
+  CharacterType,
+  DeathEvent,
+  EncounterState,
+  EnemyType,
+  LinkAttackStats,
+  PlayerData,
+  PlayerSba,
+  PlayerStatChange,
+  QuestOutcome,
+  RosterEntry,
+  SBAEvent,
+} from "@/types";
 import { create } from "zustand";
 
 interface EncounterStore {
@@ -6,7 +18,11 @@ interface EncounterStore {
   dpsChart: Record<number, number[]>;
   sbaChart: Record<number, number[]>;
   sbaEvents: SBAEvent[];
+  sbaStats: PlayerSba[];
+  linkAttacks: LinkAttackStats;
   deathEvents: DeathEvent[];
+  roster: RosterEntry[];
+  statChanges: PlayerStatChange[];
   chartLen: number;
   sbaChartLen: number;
   targets: EnemyType[];
@@ -17,6 +33,8 @@ interface EncounterStore {
   questId: number | null;
   questTimer: number | null;
   questCompleted: boolean;
+  questOutcome: QuestOutcome;
+  questDifficulty: number | null;
   setSelectedTargets: (targets: EnemyType[]) => void;
   setSelectedPlayers: (playerNames: string[]) => void;
   setSelectedPlayerTypes: (playerTypes: CharacterType[]) => void;
@@ -28,7 +46,11 @@ export interface EncounterStateResponse {
   dpsChart: Record<number, number[]>;
   sbaChart: Record<number, number[]>;
   sbaEvents: SBAEvent[];
+  sbaStats: PlayerSba[];
+  linkAttacks: LinkAttackStats;
   deathEvents: DeathEvent[];
+  roster: RosterEntry[];
+  statChanges: PlayerStatChange[];
   chartLen: number;
   sbaChartLen: number;
   targets: EnemyType[];
@@ -36,6 +58,9 @@ export interface EncounterStateResponse {
   questId: number | null;
   questTimer: number | null;
   questCompleted: boolean | null;
+  questOutcome: QuestOutcome;
+  questDifficulty: number | null;
+  missingEvents: number;
 }
 
 export const useEncounterStore = create<EncounterStore>((set) => ({
@@ -43,7 +68,11 @@ export const useEncounterStore = create<EncounterStore>((set) => ({
   dpsChart: {},
   sbaChart: {},
   sbaEvents: [],
+  sbaStats: [],
+  linkAttacks: { links: [], players: [] },
   deathEvents: [],
+  roster: [],
+  statChanges: [],
   chartLen: 0,
   sbaChartLen: 0,
   targets: [],
@@ -54,6 +83,8 @@ export const useEncounterStore = create<EncounterStore>((set) => ({
   questId: null,
   questTimer: null,
   questCompleted: false,
+  questOutcome: "Unknown",
+  questDifficulty: null,
   setSelectedTargets: (targets: EnemyType[]) => set({ selectedTargets: targets }),
   setSelectedPlayers: (playerNames: string[]) => set({ selectedPlayers: playerNames }),
   setSelectedPlayerTypes: (playerTypes: CharacterType[]) => set({ selectedPlayerTypes: playerTypes }),
@@ -65,7 +96,11 @@ export const useEncounterStore = create<EncounterStore>((set) => ({
       dpsChart: response.dpsChart,
       sbaChart: response.sbaChart,
       sbaEvents: response.sbaEvents,
+      sbaStats: response.sbaStats,
+      linkAttacks: response.linkAttacks,
       deathEvents: response.deathEvents,
+      roster: response.roster,
+      statChanges: response.statChanges,
       chartLen: response.chartLen,
       sbaChartLen: response.sbaChartLen,
       targets: response.targets,
@@ -73,6 +108,8 @@ export const useEncounterStore = create<EncounterStore>((set) => ({
       questId: response.questId,
       questTimer: response.questTimer,
       questCompleted: response.questCompleted || false,
+      questOutcome: response.questOutcome,
+      questDifficulty: response.questDifficulty,
     });
   },
 }));
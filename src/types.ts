@@ -18,6 +18,22 @@ export type CharacterType = string | { Unknown: number };
  */
 export type EnemyType = string | { Unknown: number };
 
+export type EnemyCategory = "Boss" | "Add" | "Object";
+
+export type EnemyInfo = {
+  hash: number;
+  /** Model key of the enemy, e.g. EM7700 */
+  key: string;
+  category: EnemyCategory;
+  /** Name of the enemy by language */
+  names: Record<string, string>;
+};
+
+export type EnemyCatalogEntry = EnemyInfo & {
+  /** Quests the enemy was the primary target of in the saved logs */
+  questIds: number[];
+};
+
 /**
  * ActionType represents the type of action that a skill can be.
  *
@@ -101,6 +117,15 @@ export type PlayerState = {
   lastDamageTime: number;
   /** Stats for individual skills logged */
   skillBreakdown: SkillState[];
+  /** Output relative to the player's stats, null if their stats aren't known */
+  normalized: NormalizedMetrics | null;
+};
+
+export type NormalizedMetrics = {
+  dpsPer1kAttack: number;
+  dpsPer1kPower: number;
+  /** Stun dealt per point of stun power (as stored by the game, shown multiplied by 10) */
+  stunPerStunPower: number;
 };
 
 export type ComputedPlayerState = PlayerState & {
@@ -117,6 +142,36 @@ export type EnemyState = {
   targetType: EnemyType;
   /** Total damage done to this target */
   totalDamage: number;
+  /** Stun windows of this target */
+  conditions: ConditionState[];
+  /** When this target died (UTC milliseconds since epoch), null if it didn't */
+  killedAt: number | null;
+  /** Uptime of the debuffs applied to this target */
+  debuffs: StatusState[];
+};
+
+export type EnemyCondition = "Stun";
+
+export type ConditionWindow = {
+  /** When the condition started (UTC milliseconds since epoch) */
+  startTime: number;
+  /** When the condition ended, null if it is still active */
+  endTime: number | null;
+  /** Index of the player whose hit started the condition */
+  triggerIndex: number | null;
+  /** Damage dealt to the target during this window */
+  damage: number;
+};
+
+export type ConditionState = {
+  condition: EnemyCondition;
+  /** Number of times the target entered this condition */
+  count: number;
+  /** Time spent in this condition in milliseconds */
+  totalTime: number;
+  /** Damage dealt to the target while in this condition */
+  totalDamage: number;
+  windows: ConditionWindow[];
 };
 
 export type EncounterStatus = "Waiting" | "InProgress" | "Stopped";
@@ -136,6 +191,30 @@ export type EncounterState = {
   status: EncounterStatus;
   /** Targets for this encounter */
   targets: Record<number, EnemyState>;
+  /** Number of events lost while recording, the encounter has incomplete data if non-zero */
+  missingEvents: number;
+  /** Uptime of the buffs on each party member */
+  buffs: Record<number, StatusState[]>;
+  /** Transformation uptime of each party member that can transform */
+  transformations: Record<number, TransformationState>;
+};
+
+export type TransformationWindow = {
+  startTime: number;
+  endTime: number;
+  damage: number;
+};
+
+export type TransformationState = {
+  /** Party member index */
+  index: number;
+  windows: TransformationWindow[];
+  /** Time spent transformed, in milliseconds */
+  totalTime: number;
+  /** Percentage of the encounter spent transformed */
+  uptime: number;
+  normalDamage: number;
+  transformedDamage: number;
 };
 
 export type EncounterUpdateEvent = {
@@ -187,6 +266,16 @@ export type OvermasteryInfo = {
   overmasteries: Overmastery[];
 };
 
+export type TraitTotal = {
+  traitId: number;
+  /** Total level across sigils and the weapon */
+  level: number;
+  /** Level past which the trait has no further effect, if known */
+  maxLevel: number | null;
+  /** Levels past the max level, which are wasted, null if the max level isn't known */
+  overflow: number | null;
+};
+
 export type PlayerStats = {
   level: number;
   totalHp: number;
@@ -203,9 +292,26 @@ export type PlayerData = {
   characterType: CharacterType;
   sigils: Sigil[];
   isOnline: boolean;
+  /** Party slot as reported by the game */
+  partyIndex: number;
   weaponInfo: WeaponInfo | null;
   overmasteryInfo: OvermasteryInfo | null;
   playerStats: PlayerStats | null;
+  traitTotals: TraitTotal[];
+};
+
+export type LoadoutHistoryEntry = {
+  fingerprint: string;
+  /** Milliseconds since UNIX epoch of the first log with this loadout */
+  firstSeen: number;
+  /** Milliseconds since UNIX epoch of the last log with this loadout */
+  lastSeen: number;
+  /** Logs with this loadout, oldest first */
+  logIds: number[];
+  bestDps: number | null;
+  averageDps: number | null;
+  /** The player as they were in the last log with this loadout */
+  player: PlayerData | null;
 };
 
 export type PartyUpdateEvent = {
@@ -213,6 +319,24 @@ export type PartyUpdateEvent = {
   payload: Array<PlayerData | null>;
 };
 
+/** `Unavailable` hooks haven't been located in the game yet, so they were never attempted */
+export type HookState = "Installed" | "Failed" | "Unavailable";
+
+export type HookInstallStatus = {
+  /** Name of the hook, e.g. `damage` or `sba_update` */
+  name: string;
+  state: HookState;
+  /** Reason the hook could not be installed */
+  error: string | null;
+};
+
+export type HookStatusEvent = {
+  event: string;
+  payload: {
+    hooks: HookInstallStatus[];
+  };
+};
+
 export enum MeterColumns {
   Name = "name",
   DPS = "dps",
@@ -221,6 +345,8 @@ export enum MeterColumns {
   SBA = "sba",
   TotalStunValue = "total-stun-value",
   StunPerSecond = "stun-per-second",
+  DPSPerAttack = "dps-per-attack",
+  StunPerStunPower = "stun-per-stun-power",
 }
 
 export type SortType = MeterColumns;
@@ -228,6 +354,8 @@ export type SortType = MeterColumns;
 export type LogSortType = "time" | "duration" | "quest-elapsed-time";
 export type SortDirection = "asc" | "desc";
 
+export type QuestOutcome = "Unknown" | "Completed";
+
 export type Log = {
   id: number;
   name: string;
@@ -246,6 +374,7 @@ export type Log = {
   questId: number | null;
   questElapsedTime: number | null;
   questCompleted: boolean;
+  questOutcome: QuestOutcome | null;
 };
 
 export type SBAEvent = [
@@ -258,3 +387,132 @@ export type SBAEvent = [
 ];
 
 export type DeathEvent = [number, { OnDeathEvent: { actor_index: number; death_counter: number } }];
+
+export type SkillRotation = {
+  skillId: number;
+  casts: number;
+  /** Time between consecutive casts in milliseconds */
+  intervals: number[];
+  averageInterval: number | null;
+  shortestInterval: number | null;
+  /** Cooldown reported by the game in milliseconds, if known */
+  cooldown: number | null;
+  /** Average time the skill was ready but not cast in milliseconds, needs the cooldown to be known */
+  averageDelay: number | null;
+};
+
+export type PlayerRotation = {
+  index: number;
+  characterType: CharacterType;
+  /** Every cast in order, as milliseconds since the first hit (negative before it) and the skill ID */
+  timeline: [number, number][];
+  skills: SkillRotation[];
+};
+
+export type StatusWindow = {
+  /** When the status was applied (UTC milliseconds since epoch) */
+  startTime: number;
+  /** When the status ended, null if it is still active */
+  endTime: number | null;
+  /** Index of the party member that applied the status */
+  applierIndex: number | null;
+};
+
+export type StatusState = {
+  /** Hash ID of the status */
+  statusId: number;
+  /** Last known skill that applied the status */
+  skillId: number | null;
+  /** Number of times the status was applied, including refreshes */
+  applications: number;
+  /** Time the status was active in milliseconds */
+  totalTime: number;
+  /** Time the status was active, by the party member that applied it */
+  timeByApplier: [number, number][];
+  windows: StatusWindow[];
+};
+
+/** Statuses applied by the same skill group (e.g. every level of Gran's Armor Break), counted as one */
+export type StatusGroup = {
+  /** Character of the party member that applied the statuses, null if unknown */
+  characterType: CharacterType | null;
+  /** Skill group the applying skill belongs to, null if it has none */
+  skillGroup: string | null;
+  /** Last known skill that applied the statuses */
+  skillId: number | null;
+  statusIds: number[];
+  applications: number;
+  /** Time any of the statuses was active in milliseconds, overlapping windows are only counted once */
+  totalTime: number;
+  /** Time the statuses were active, by the party member that applied them */
+  timeByApplier: [number, number][];
+};
+
+export type RosterEntry = {
+  actorIndex: number;
+  partyIndex: number;
+  isOnline: boolean;
+  /** Milliseconds since the start of the encounter */
+  joinedAt: number;
+};
+
+export type PlayerStatChange = {
+  actorIndex: number;
+  /** Milliseconds since the start of the encounter */
+  timestamp: number;
+  before: PlayerStats | null;
+  after: PlayerStats | null;
+  /** Whether the weapon, sigils or overmasteries changed as well */
+  loadoutChanged: boolean;
+};
+
+export type PlayerSba = {
+  /** Party member index */
+  index: number;
+  sbaCount: number;
+  /** Only known for the local player */
+  chainCount: number;
+  /** Milliseconds taken to fill the gauge after each Skybound Art */
+  fillTimes: number[];
+  averageFillTime: number | null;
+  /** Milliseconds spent with a full gauge */
+  timeAtFull: number;
+  /** Percentage of the encounter's chains the player took part in, only chains the local player continued are known */
+  chainParticipation: number;
+  sbaDamage: number;
+};
+
+export type LinkParticipant = {
+  /** Party member index */
+  index: number;
+  hits: number;
+  damage: number;
+};
+
+export type LinkAttack = {
+  /** Milliseconds since the start of the encounter */
+  startTime: number;
+  /** Milliseconds since the start of the encounter */
+  endTime: number;
+  /** Party member index of the player whose link attack hit first */
+  firstHitBy: number;
+  participants: LinkParticipant[];
+  totalDamage: number;
+};
+
+export type PlayerLinkAttacks = {
+  /** Party member index */
+  index: number;
+  /** Number of links the player hit first in */
+  firstHits: number;
+  participated: number;
+  /** Percentage of the encounter's links the player joined in */
+  participation: number;
+  totalDamage: number;
+  averageDamage: number;
+};
+
+export type LinkAttackStats = {
+  links: LinkAttack[];
+  players: PlayerLinkAttacks[];
+};
@@ -1,5 +1,6 @@
 import { describe, expect, it } from "vitest";
-import { toHash, toHashString } from "./utils";
+import { StatusState } from "./types";
+import { groupStatuses, toHash, toHashString } from "./utils";
 
 describe("utils", () => {
   it("toHash", () => {
@@ -11,4 +12,34 @@ describe("utils", () => {
     expect(toHashString(1)).toBe("00000001");
     expect(toHashString(255)).toBe("000000ff");
   });
+
+  it("groupStatuses", () => {
+    const status = (statusId: number, skillId: number, startTime: number, endTime: number | null): StatusState => ({
+      statusId,
+      skillId,
+      applications: 1,
+      totalTime: endTime === null ? 0 : endTime - startTime,
+      timeByApplier: endTime === null ? [] : [[0, endTime - startTime]],
+      windows: [{ startTime, endTime, applierIndex: 0 }],
+    });
+
+    const skillGroups = { Pl0000: { "armor-break": { skills: [1501, 1502] } } };
+    const groups = groupStatuses(
+      [status(1, 1501, 0, 1000), status(2, 1502, 500, 2000), status(3, 2001, 0, null)],
+      () => "Pl0000",
+      skillGroups,
+      3000
+    );
+
+    expect(groups).toHaveLength(2);
+    expect(groups[0]).toMatchObject({
+      skillGroup: "armor-break",
+      statusIds: [1, 2],
+      applications: 2,
+      totalTime: 2000,
+      timeByApplier: [[0, 2500]],
+    });
+    // Skills without a group are kept on their own, and active windows last until the end.
+    expect(groups[1]).toMatchObject({ skillGroup: null, statusIds: [3], totalTime: 3000 });
+  });
 });
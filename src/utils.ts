@@ -13,7 +13,11 @@ import {
   SkillState,
   SortDirection,
   SortType,
+  StatusGroup,
+  StatusState,
+  StatusWindow,
 } from "./types";
+import type { SkillGroupMapping } from "./assets/skill-groups";
 
 import { t } from "i18next";
 import { useEffect, useRef } from "react";
@@ -110,6 +114,83 @@ const tryParseInt = (intString: string | number, defaultValue = 0) => {
 
 /// Takes a number and returns a shortened version of it that is friendlier to read.
 /// For example, 1200 would be returned as 1.2k, 1200000 as 1.2m, and so on.
+/** Time covered by the windows, counting overlaps once. Windows that are still active last until `endTime`. */
+const coveredTime = (windows: StatusWindow[], endTime: number): number => {
+  const sorted = windows
+    .map((window) => [window.startTime, window.endTime ?? endTime])
+    .sort(([a], [b]) => a - b);
+
+  let total = 0;
+  let coveredUntil = -Infinity;
+
+  for (const [start, end] of sorted) {
+    total += Math.max(0, end - Math.max(start, coveredUntil));
+    coveredUntil = Math.max(coveredUntil, end);
+  }
+
+  return total;
+};
+
+/**
+ * Groups statuses by the skill group of the skill that applied them, so that e.g. every level of Gran's Armor Break
+ * counts as one debuff. Statuses from skills without a group are kept on their own.
+ */
+export const groupStatuses = (
+  statuses: StatusState[],
+  characterTypeOf: (index: number) => CharacterType | undefined,
+  skillGroups: SkillGroupMapping,
+  endTime: number
+): StatusGroup[] => {
+  const groups = new Map<string, StatusGroup & { windows: StatusWindow[] }>();
+
+  for (const status of statuses) {
+    const applierIndex = status.windows.find((window) => window.applierIndex !== null)?.applierIndex ?? null;
+    const characterType = applierIndex === null ? null : characterTypeOf(applierIndex) ?? null;
+    const characterGroups = typeof characterType === "string" ? skillGroups[characterType] ?? {} : {};
+    const skillGroup =
+      Object.keys(characterGroups).find(
+        (group) => status.skillId !== null && characterGroups[group].skills.includes(status.skillId)
+      ) ?? null;
+
+    const key = skillGroup === null ? `status-${status.statusId}` : `${characterType}-${skillGroup}`;
+    let group = groups.get(key);
+
+    if (!group) {
+      group = {
+        characterType,
+        skillGroup,
+        skillId: null,
+        statusIds: [],
+        applications: 0,
+        totalTime: 0,
+        timeByApplier: [],
+        windows: [],
+      };
+      groups.set(key, group);
+    }
+
+    group.skillId = status.skillId ?? group.skillId;
+    group.statusIds.push(status.statusId);
+    group.applications += status.applications;
+    group.windows.push(...status.windows);
+
+    for (const [index, time] of status.timeByApplier) {
+      const applier = group.timeByApplier.find(([applierIndex]) => applierIndex === index);
+
+      if (applier) {
+        applier[1] += time;
+      } else {
+        group.timeByApplier.push([index, time]);
+      }
+    }
+  }
+
+  return Array.from(groups.values()).map(({ windows, ...group }) => ({
+    ...group,
+    totalTime: coveredTime(windows, endTime),
+  }));
+};
+
 export const humanizeNumbers = (n: number) => {
   if (n >= 1e3 && n < 1e6) return [+(n / 1e3).toFixed(1), "k"];
   if (n >= 1e6 && n < 1e9) return [+(n / 1e6).toFixed(1), "m"];
@@ -174,6 +255,12 @@ export const sortPlayers = (players: ComputedPlayerState[], sortType: SortType,
       return sortDirection === "asc" ? a?.totalStunValue - b?.totalStunValue : b?.totalStunValue - a?.totalStunValue;
     } else if (sortType === MeterColumns.StunPerSecond) {
       return sortDirection === "asc" ? a?.stunPerSecond - b?.stunPerSecond : b?.stunPerSecond - a?.stunPerSecond;
+    } else if (sortType === MeterColumns.DPSPerAttack) {
+      const [aValue, bValue] = [a?.normalized?.dpsPer1kAttack || 0, b?.normalized?.dpsPer1kAttack || 0];
+      return sortDirection === "asc" ? aValue - bValue : bValue - aValue;
+    } else if (sortType === MeterColumns.StunPerStunPower) {
+      const [aValue, bValue] = [a?.normalized?.stunPerStunPower || 0, b?.normalized?.stunPerStunPower || 0];
+      return sortDirection === "asc" ? aValue - bValue : bValue - aValue;
     }
 
     return 0;